@@ -0,0 +1,1206 @@
+//! Token-2022 instructions that pinocchio-token-2022 has no builder for.
+//!
+//! Each builder mirrors the pinocchio-token-2022 ones: accounts and arguments
+//! are plain fields, and the instruction data is written into an uninitialized
+//! stack buffer right before the CPI.
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
+
+use pinocchio::{
+    account::AccountView,
+    cpi::{invoke_signed, invoke_signed_with_bounds, invoke_signed_with_slice, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    Address, ProgramResult,
+};
+
+const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
+
+const UNINIT_INSTRUCTION_ACCOUNT: MaybeUninit<InstructionAccount> =
+    MaybeUninit::<InstructionAccount>::uninit();
+
+const UNINIT_VIEW: MaybeUninit<&AccountView> = MaybeUninit::<&AccountView>::uninit();
+
+#[inline(always)]
+fn write_bytes(destination: &mut [MaybeUninit<u8>], source: &[u8]) {
+    let len = destination.len().min(source.len());
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            source.as_ptr(),
+            destination.as_mut_ptr() as *mut u8,
+            len,
+        );
+    }
+}
+
+/// Sets one of the Token-2022 extension authorities (TransferFeeConfig = 4
+/// through Pause = 16), which pinocchio-token-2022's `AuthorityType` cannot
+/// express.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint or account to change the authority of.
+///   1. `[SIGNER]` The current authority of the mint or account.
+pub struct SetExtensionAuthority<'a, 'b> {
+    /// Mint or account to change the authority of.
+    pub account: &'a AccountView,
+    /// The current authority of the mint or account.
+    pub authority: &'a AccountView,
+    /// Raw Token-2022 authority type byte.
+    pub authority_type: u8,
+    /// The new authority, or `None` to remove it.
+    pub new_authority: Option<&'a Address>,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl SetExtensionAuthority<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.account.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: authority_type (1 byte, u8)
+        // -  [2]: new_authority presence flag (1 byte, u8)
+        // -  [3..35]: new_authority (optional, 32 bytes, Address)
+        let mut instruction_data = [UNINIT_BYTE; 35];
+        let mut length = 3;
+
+        write_bytes(&mut instruction_data, &[6, self.authority_type]);
+
+        if let Some(new_authority) = self.new_authority {
+            write_bytes(&mut instruction_data[2..3], &[1]);
+            write_bytes(&mut instruction_data[3..], new_authority.as_ref());
+            length = 35;
+        } else {
+            write_bytes(&mut instruction_data[2..3], &[0]);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        invoke_signed(&instruction, &[self.account, self.authority], signers)
+    }
+}
+
+/// Creates the Token-2022 native mint, funded by `payer`.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Funding account (must be a system account).
+///   1. `[WRITE]` The native mint address.
+///   2. `[]` System program for mint account funding.
+pub struct CreateNativeMint<'a, 'b> {
+    /// Funding account.
+    pub payer: &'a AccountView,
+    /// The native mint address.
+    pub native_mint: &'a AccountView,
+    /// System program.
+    pub system_program: &'a AccountView,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl CreateNativeMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 3] = [
+            InstructionAccount::writable_signer(self.payer.address()),
+            InstructionAccount::writable(self.native_mint.address()),
+            InstructionAccount::readonly(self.system_program.address()),
+        ];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &[31],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.payer, self.native_mint, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Moves the lamports a mint, token account or multisig holds above its
+/// rent-exempt minimum to `destination`.
+///
+/// ### Accounts:
+///   0. `[WRITE]` Source account owned by the token program.
+///   1. `[WRITE]` Destination account.
+///   2. `[SIGNER]` Authority of the source account.
+pub struct WithdrawExcessLamports<'a, 'b> {
+    /// Source account.
+    pub source: &'a AccountView,
+    /// Destination account.
+    pub destination: &'a AccountView,
+    /// Authority of the source account.
+    pub authority: &'a AccountView,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl WithdrawExcessLamports<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 3] = [
+            InstructionAccount::writable(self.source.address()),
+            InstructionAccount::writable(self.destination.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &[38],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.source, self.destination, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Token-2022 instruction discriminator of the transfer-fee extension; the
+/// byte after it selects the transfer-fee instruction.
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+
+/// Initializes the transfer fee on a new mint. Must run before
+/// InitializeMint, on a mint account sized for the `TransferFeeConfig`
+/// extension.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeTransferFeeConfig<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Authority that can set the transfer fee.
+    pub transfer_fee_config_authority: Option<&'b Address>,
+    /// Authority that can withdraw withheld fees.
+    pub withdraw_withheld_authority: Option<&'b Address>,
+    /// Fee in basis points of the transfer amount.
+    pub transfer_fee_basis_points: u16,
+    /// Maximum fee per transfer.
+    pub maximum_fee: u64,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl InitializeTransferFeeConfig<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::writable(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: extension discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2]: transfer_fee_config_authority presence flag (1 byte, u8)
+        // -  [3..35]: transfer_fee_config_authority (optional, 32 bytes, Address)
+        // -  [..]: withdraw_withheld_authority presence flag (1 byte, u8)
+        // -  [..]: withdraw_withheld_authority (optional, 32 bytes, Address)
+        // -  [..]: transfer_fee_basis_points (2 bytes, u16)
+        // -  [..]: maximum_fee (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 78];
+        let mut length = 2;
+
+        write_bytes(&mut instruction_data, &[TRANSFER_FEE_EXTENSION, 0]);
+
+        for authority in [
+            self.transfer_fee_config_authority,
+            self.withdraw_withheld_authority,
+        ] {
+            if let Some(authority) = authority {
+                write_bytes(&mut instruction_data[length..], &[1]);
+                write_bytes(&mut instruction_data[length + 1..], authority.as_ref());
+                length += 33;
+            } else {
+                write_bytes(&mut instruction_data[length..], &[0]);
+                length += 1;
+            }
+        }
+
+        write_bytes(
+            &mut instruction_data[length..],
+            &self.transfer_fee_basis_points.to_le_bytes(),
+        );
+        write_bytes(
+            &mut instruction_data[length + 2..],
+            &self.maximum_fee.to_le_bytes(),
+        );
+        length += 10;
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Transfers tokens, checking the mint's decimals and the fee the
+/// transfer-fee extension withholds in the destination.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[SIGNER]` The source account's owner/delegate.
+pub struct TransferCheckedWithFee<'a, 'b> {
+    /// Sender account.
+    pub from: &'a AccountView,
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Recipient account.
+    pub to: &'a AccountView,
+    /// Authority account.
+    pub authority: &'a AccountView,
+    /// Amount of micro-tokens to transfer.
+    pub amount: u64,
+    /// Decimal places.
+    pub decimals: u8,
+    /// Expected fee, which must match the one the mint calculates.
+    pub fee: u64,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl TransferCheckedWithFee<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 4] = [
+            InstructionAccount::writable(self.from.address()),
+            InstructionAccount::readonly(self.mint.address()),
+            InstructionAccount::writable(self.to.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: extension discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..10]: amount (8 bytes, u64)
+        // -  [10]: decimals (1 byte, u8)
+        // -  [11..19]: fee (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 19];
+
+        write_bytes(&mut instruction_data, &[TRANSFER_FEE_EXTENSION, 1]);
+        write_bytes(&mut instruction_data[2..10], &self.amount.to_le_bytes());
+        write_bytes(&mut instruction_data[10..11], &[self.decimals]);
+        write_bytes(&mut instruction_data[11..19], &self.fee.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 19) },
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.from, self.mint, self.to, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Moves the fees withheld in a mint to a token account of that mint.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[SIGNER]` The mint's withdraw withheld authority.
+pub struct WithdrawWithheldTokensFromMint<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Fee receiver account.
+    pub destination: &'a AccountView,
+    /// Withdraw withheld authority.
+    pub authority: &'a AccountView,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl WithdrawWithheldTokensFromMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 3] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::writable(self.destination.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &[TRANSFER_FEE_EXTENSION, 2],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.mint, self.destination, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Most source accounts `WithdrawWithheldTokensFromAccounts` and
+/// `HarvestWithheldTokensToMint` take.
+const MAX_WITHHELD_SOURCES: usize = 32;
+
+/// Moves the fees withheld in token accounts to a token account of the same
+/// mint.
+///
+/// ### Accounts:
+///   0. `[]` The token mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[SIGNER]` The mint's withdraw withheld authority.
+///   3. ..`3+N`. `[WRITE]` The N source accounts to withdraw from.
+pub struct WithdrawWithheldTokensFromAccounts<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Fee receiver account.
+    pub destination: &'a AccountView,
+    /// Withdraw withheld authority.
+    pub authority: &'a AccountView,
+    /// Source accounts, at most `MAX_WITHHELD_SOURCES`.
+    pub sources: &'a [AccountView],
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl WithdrawWithheldTokensFromAccounts<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.sources.len() > MAX_WITHHELD_SOURCES {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let account_count = 3 + self.sources.len();
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; 3 + MAX_WITHHELD_SOURCES];
+        instruction_accounts[0].write(InstructionAccount::readonly(self.mint.address()));
+        instruction_accounts[1].write(InstructionAccount::writable(self.destination.address()));
+        instruction_accounts[2].write(InstructionAccount::readonly_signer(
+            self.authority.address(),
+        ));
+        for (instruction_account, source) in
+            instruction_accounts[3..].iter_mut().zip(self.sources)
+        {
+            instruction_account.write(InstructionAccount::writable(source.address()));
+        }
+
+        let mut account_views = [UNINIT_VIEW; 3 + MAX_WITHHELD_SOURCES];
+        account_views[0].write(self.mint);
+        account_views[1].write(self.destination);
+        account_views[2].write(self.authority);
+        for (account_view, source) in account_views[3..].iter_mut().zip(self.sources) {
+            account_view.write(source);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            // The source count fits in a u8 since it is bounded above.
+            data: &[TRANSFER_FEE_EXTENSION, 3, self.sources.len() as u8],
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_slice(&instruction, account_views, signers)
+    }
+}
+
+/// Moves the fees withheld in token accounts to their mint. Anyone can
+/// harvest; accounts that cannot be harvested are skipped.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The token mint.
+///   1. ..`1+N`. `[WRITE]` The N source accounts to harvest from.
+pub struct HarvestWithheldTokensToMint<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Source accounts, at most `MAX_WITHHELD_SOURCES`.
+    pub sources: &'a [AccountView],
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl HarvestWithheldTokensToMint<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.sources.len() > MAX_WITHHELD_SOURCES {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let account_count = 1 + self.sources.len();
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; 1 + MAX_WITHHELD_SOURCES];
+        instruction_accounts[0].write(InstructionAccount::writable(self.mint.address()));
+        for (instruction_account, source) in
+            instruction_accounts[1..].iter_mut().zip(self.sources)
+        {
+            instruction_account.write(InstructionAccount::writable(source.address()));
+        }
+
+        let mut account_views = [UNINIT_VIEW; 1 + MAX_WITHHELD_SOURCES];
+        account_views[0].write(self.mint);
+        for (account_view, source) in account_views[1..].iter_mut().zip(self.sources) {
+            account_view.write(source);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: &[TRANSFER_FEE_EXTENSION, 4],
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_slice(&instruction, account_views, signers)
+    }
+}
+
+/// Sets the transfer fee, which takes effect two epochs later.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's transfer fee config authority.
+pub struct SetTransferFee<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Transfer fee config authority.
+    pub authority: &'a AccountView,
+    /// Fee in basis points of the transfer amount.
+    pub transfer_fee_basis_points: u16,
+    /// Maximum fee per transfer.
+    pub maximum_fee: u64,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl SetTransferFee<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: extension discriminator (1 byte, u8)
+        // -  [1]: instruction discriminator (1 byte, u8)
+        // -  [2..4]: transfer_fee_basis_points (2 bytes, u16)
+        // -  [4..12]: maximum_fee (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 12];
+
+        write_bytes(&mut instruction_data, &[TRANSFER_FEE_EXTENSION, 5]);
+        write_bytes(
+            &mut instruction_data[2..4],
+            &self.transfer_fee_basis_points.to_le_bytes(),
+        );
+        write_bytes(&mut instruction_data[4..12], &self.maximum_fee.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 12) },
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.authority], signers)
+    }
+}
+
+/// Most accounts `TransferCheckedWithHook` passes for the hook invocation: the
+/// hook program, its extra-account-metas PDA and up to 16 extra accounts.
+const MAX_HOOK_ACCOUNTS: usize = 2 + 16;
+
+/// Transfers tokens of a mint with a transfer hook, passing Token-2022 the
+/// accounts it needs to invoke the hook after the four TransferChecked ones.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[SIGNER]` The source account's owner/delegate.
+///   4. ..`4+N`. The hook program, its extra-account-metas PDA and the extra
+///      accounts listed there, forwarded with their own privileges.
+pub struct TransferCheckedWithHook<'a, 'b> {
+    /// Sender account.
+    pub from: &'a AccountView,
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Recipient account.
+    pub to: &'a AccountView,
+    /// Authority account.
+    pub authority: &'a AccountView,
+    /// Accounts the hook invocation needs, at most `MAX_HOOK_ACCOUNTS`.
+    pub hook_accounts: &'a [AccountView],
+    /// Amount of micro-tokens to transfer.
+    pub amount: u64,
+    /// Decimal places.
+    pub decimals: u8,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl TransferCheckedWithHook<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.hook_accounts.len() > MAX_HOOK_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let account_count = 4 + self.hook_accounts.len();
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; 4 + MAX_HOOK_ACCOUNTS];
+        instruction_accounts[0].write(InstructionAccount::writable(self.from.address()));
+        instruction_accounts[1].write(InstructionAccount::readonly(self.mint.address()));
+        instruction_accounts[2].write(InstructionAccount::writable(self.to.address()));
+        instruction_accounts[3].write(InstructionAccount::readonly_signer(
+            self.authority.address(),
+        ));
+        for (instruction_account, account) in
+            instruction_accounts[4..].iter_mut().zip(self.hook_accounts)
+        {
+            instruction_account.write(InstructionAccount::from(account));
+        }
+
+        let mut account_views = [UNINIT_VIEW; 4 + MAX_HOOK_ACCOUNTS];
+        account_views[0].write(self.from);
+        account_views[1].write(self.mint);
+        account_views[2].write(self.to);
+        account_views[3].write(self.authority);
+        for (account_view, account) in account_views[4..].iter_mut().zip(self.hook_accounts) {
+            account_view.write(account);
+        }
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..9]: amount (8 bytes, u64)
+        // -  [9]: decimals (1 byte, u8)
+        let mut instruction_data = [UNINIT_BYTE; 10];
+
+        write_bytes(&mut instruction_data, &[12]);
+        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
+        write_bytes(&mut instruction_data[9..10], &[self.decimals]);
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 10) },
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_slice(&instruction, account_views, signers)
+    }
+}
+
+/// Mint extension an `InitializeMintExtension` initializes, with its
+/// arguments.
+pub enum MintExtensionInit<'a> {
+    /// InitializeMintCloseAuthority.
+    MintCloseAuthority {
+        close_authority: Option<&'a Address>,
+    },
+    /// InitializeNonTransferableMint.
+    NonTransferable,
+    /// InitializePermanentDelegate.
+    PermanentDelegate { delegate: &'a Address },
+    /// DefaultAccountState Initialize, with the raw account state byte.
+    DefaultAccountState { state: u8 },
+    /// InterestBearingMint Initialize.
+    InterestBearing {
+        rate_authority: Option<&'a Address>,
+        rate: i16,
+    },
+    /// MetadataPointer Initialize.
+    MetadataPointer {
+        authority: Option<&'a Address>,
+        metadata_address: Option<&'a Address>,
+    },
+    /// GroupPointer Initialize.
+    GroupPointer {
+        authority: Option<&'a Address>,
+        group_address: Option<&'a Address>,
+    },
+}
+
+/// Initializes a mint extension. Must run before InitializeMint, on a mint
+/// account sized for the extension.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeMintExtension<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Extension to initialize.
+    pub extension: MintExtensionInit<'b>,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl InitializeMintExtension<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::writable(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: extension instruction discriminator, for the extensions
+        //         with more than one instruction (1 byte, u8)
+        // -  [..]: extension arguments (up to 64 bytes); an optional address
+        //          is a presence flag and the address for
+        //          InitializeMintCloseAuthority, and 32 zero bytes for None
+        //          elsewhere
+        let mut instruction_data = [UNINIT_BYTE; 66];
+        let length = match self.extension {
+            MintExtensionInit::MintCloseAuthority { close_authority } => {
+                write_bytes(&mut instruction_data, &[25]);
+                if let Some(close_authority) = close_authority {
+                    write_bytes(&mut instruction_data[1..2], &[1]);
+                    write_bytes(&mut instruction_data[2..34], close_authority.as_ref());
+                    34
+                } else {
+                    write_bytes(&mut instruction_data[1..2], &[0]);
+                    2
+                }
+            }
+            MintExtensionInit::NonTransferable => {
+                write_bytes(&mut instruction_data, &[32]);
+                1
+            }
+            MintExtensionInit::PermanentDelegate { delegate } => {
+                write_bytes(&mut instruction_data, &[35]);
+                write_bytes(&mut instruction_data[1..33], delegate.as_ref());
+                33
+            }
+            MintExtensionInit::DefaultAccountState { state } => {
+                write_bytes(&mut instruction_data, &[28, 0, state]);
+                3
+            }
+            MintExtensionInit::InterestBearing {
+                rate_authority,
+                rate,
+            } => {
+                write_bytes(&mut instruction_data, &[33, 0]);
+                write_optional_address(&mut instruction_data[2..34], rate_authority);
+                write_bytes(&mut instruction_data[34..36], &rate.to_le_bytes());
+                36
+            }
+            MintExtensionInit::MetadataPointer {
+                authority,
+                metadata_address,
+            } => {
+                write_bytes(&mut instruction_data, &[39, 0]);
+                write_optional_address(&mut instruction_data[2..34], authority);
+                write_optional_address(&mut instruction_data[34..66], metadata_address);
+                66
+            }
+            MintExtensionInit::GroupPointer {
+                authority,
+                group_address,
+            } => {
+                write_bytes(&mut instruction_data, &[40, 0]);
+                write_optional_address(&mut instruction_data[2..34], authority);
+                write_optional_address(&mut instruction_data[34..66], group_address);
+                66
+            }
+        };
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Writes an `OptionalNonZeroPubkey`: the address, or 32 zero bytes for
+/// `None`.
+#[inline(always)]
+fn write_optional_address(destination: &mut [MaybeUninit<u8>], address: Option<&Address>) {
+    match address {
+        Some(address) => write_bytes(destination, address.as_ref()),
+        None => write_bytes(destination, &[0; 32]),
+    }
+}
+
+/// Requires memos on transfers into a token account. The account must have
+/// room for the MemoTransfer extension.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The account to require memos on.
+///   1. `[SIGNER]` The account's owner.
+pub struct EnableRequiredMemoTransfers<'a, 'b> {
+    /// Token account.
+    pub account: &'a AccountView,
+    /// The account's owner.
+    pub owner: &'a AccountView,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl EnableRequiredMemoTransfers<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.account.address()),
+            InstructionAccount::readonly_signer(self.owner.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: MemoTransfer instruction discriminator (1 byte, u8)
+        let instruction_data = [30, 0];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.account, self.owner], signers)
+    }
+}
+
+/// Most signer accounts `Memo` passes to the memo program.
+const MAX_MEMO_SIGNERS: usize = 16;
+
+/// Logs `memo` through the SPL Memo program, which also checks that every
+/// account passed to it signed.
+///
+/// ### Accounts:
+///   0. ..`N`. `[SIGNER]` The N memo signers
+pub struct Memo<'a> {
+    /// Memo program.
+    pub program: &'a AccountView,
+    /// Memo signer accounts, at most `MAX_MEMO_SIGNERS`.
+    pub signers: &'a [AccountView],
+    /// Memo, which the memo program requires to be UTF-8.
+    pub memo: &'a [u8],
+}
+
+impl Memo<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_count = self.signers.len();
+        if account_count > MAX_MEMO_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; MAX_MEMO_SIGNERS];
+        for (instruction_account, signer) in instruction_accounts.iter_mut().zip(self.signers) {
+            instruction_account.write(InstructionAccount::readonly_signer(signer.address()));
+        }
+
+        let mut account_views = [UNINIT_VIEW; MAX_MEMO_SIGNERS];
+        for (account_view, signer) in account_views.iter_mut().zip(self.signers) {
+            account_view.write(signer);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.program.address(),
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: self.memo,
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_bounds::<MAX_MEMO_SIGNERS>(&instruction, account_views, signers)
+    }
+}
+
+/// Updates the interest rate of an interest-bearing mint. Token-2022 folds
+/// the current rate into the time-weighted average rate first.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's rate authority.
+pub struct UpdateRate<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// The mint's rate authority.
+    pub authority: &'a AccountView,
+    /// New interest rate, in basis points.
+    pub rate: i16,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl UpdateRate<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: InterestBearingMint instruction discriminator (1 byte, u8)
+        // -  [2..4]: rate (2 bytes, i16)
+        let mut instruction_data = [UNINIT_BYTE; 4];
+
+        write_bytes(&mut instruction_data, &[33, 1]);
+        write_bytes(&mut instruction_data[2..4], &self.rate.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 4) },
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.authority], signers)
+    }
+}
+
+/// Schedules a new multiplier for a scaled UI amount mint. It takes effect
+/// right away when `effective_timestamp` has already passed.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's multiplier authority.
+pub struct UpdateMultiplier<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// The mint's multiplier authority.
+    pub authority: &'a AccountView,
+    /// New multiplier.
+    pub multiplier: f64,
+    /// Unix timestamp the new multiplier takes effect at.
+    pub effective_timestamp: i64,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl UpdateMultiplier<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: ScaledUiAmount instruction discriminator (1 byte, u8)
+        // -  [2..10]: multiplier (8 bytes, f64)
+        // -  [10..18]: effective_timestamp (8 bytes, i64)
+        let mut instruction_data = [UNINIT_BYTE; 18];
+
+        write_bytes(&mut instruction_data, &[43, 1]);
+        write_bytes(&mut instruction_data[2..10], &self.multiplier.to_le_bytes());
+        write_bytes(
+            &mut instruction_data[10..18],
+            &self.effective_timestamp.to_le_bytes(),
+        );
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 18) },
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.authority], signers)
+    }
+}
+
+/// Changes the state new token accounts of a mint start in.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's freeze authority.
+pub struct UpdateDefaultAccountState<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// The mint's freeze authority.
+    pub freeze_authority: &'a AccountView,
+    /// New default account state, as its raw byte.
+    pub state: u8,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl UpdateDefaultAccountState<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::readonly_signer(self.freeze_authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: DefaultAccountState instruction discriminator (1 byte, u8)
+        // -  [2]: state (1 byte, u8)
+        let instruction_data = [28, 1, self.state];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.freeze_authority], signers)
+    }
+}
+
+/// Pauses or resumes a pausable mint. While it is paused, Token-2022 rejects
+/// its transfers, mints and burns.
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's pause authority.
+pub struct SetPaused<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// The mint's pause authority.
+    pub authority: &'a AccountView,
+    /// Whether to pause (Pause) or resume (Resume) the mint.
+    pub paused: bool,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl SetPaused<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 2] = [
+            InstructionAccount::writable(self.mint.address()),
+            InstructionAccount::readonly_signer(self.authority.address()),
+        ];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: Pausable instruction discriminator, Pause (1) or Resume
+        //         (2) (1 byte, u8)
+        let instruction_data = [44, if self.paused { 1 } else { 2 }];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &instruction_data,
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.authority], signers)
+    }
+}
+
+/// Maximum length of the UI amount string `UiAmountToAmount` accepts.
+pub const MAX_UI_AMOUNT_LEN: usize = 128;
+
+/// Gets the size of a token account for `mint`. The token program sets it as
+/// return data (8 bytes, u64).
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct GetAccountDataSize<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl GetAccountDataSize<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: &[21],
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Converts an amount of tokens into a UI amount string using the mint's
+/// decimals. The token program sets the string as return data.
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct AmountToUiAmount<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Amount of micro-tokens to convert.
+    pub amount: u64,
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl AmountToUiAmount<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..9]: amount (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 9];
+
+        write_bytes(&mut instruction_data, &[23]);
+        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 9) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Converts a UI amount string into an amount of tokens using the mint's
+/// decimals. The token program sets the amount as return data (8 bytes, u64).
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct UiAmountToAmount<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// UTF-8 UI amount, at most `MAX_UI_AMOUNT_LEN` bytes.
+    pub ui_amount: &'b [u8],
+    /// Token program.
+    pub token_program: &'b Address,
+}
+
+impl UiAmountToAmount<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.ui_amount.len() > MAX_UI_AMOUNT_LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..]: ui_amount (up to MAX_UI_AMOUNT_LEN bytes, UTF-8)
+        let mut instruction_data = [UNINIT_BYTE; 1 + MAX_UI_AMOUNT_LEN];
+        let length = 1 + self.ui_amount.len();
+
+        write_bytes(&mut instruction_data, &[24]);
+        write_bytes(&mut instruction_data[1..], self.ui_amount);
+
+        let instruction = InstructionView {
+            program_id: self.token_program,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
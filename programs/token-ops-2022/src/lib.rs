@@ -52,12 +52,132 @@
 //!
 //! 14 = SetAuthority (authority_type: u8, has_new_authority: u8)
 //!     Accounts: [account, authority, new_authority?, token_program]
+//!     authority_type must be 0-3 (MintTokens, FreezeAccount, AccountOwner,
+//!     CloseAccount) or one of the Token-2022 extension authorities 4-16
+//!     (TransferFeeConfig through Pause); anything else fails with
+//!     InvalidInstructionData.
+//!
+//! 15-17 are token-ops only (Script, TransferWithRemaining, Forward).
+//!
+//! 18 = MintToChecked (amount: u64, decimals: u8)
+//!     Accounts: [mint, destination, mint_authority, token_program]
+//!
+//! 19 = BurnChecked (amount: u64, decimals: u8)
+//!     Accounts: [source, mint, authority, token_program]
+//!
+//! 20 = ApproveChecked (amount: u64, decimals: u8)
+//!     Accounts: [source, mint, delegate, authority, token_program]
+//!
+//! 21-24 are token-ops only (InitializeMultisig, InitializeMultisig2,
+//! Multisig, PdaSigned).
+//!
+//! 25 = SyncNative
+//!     Accounts: [native_token, token_program]
+//!
+//! 26 = CreateNativeMint
+//!     Accounts: [payer, native_mint, system_program, token_program]
+//!
+//! 27 = WithdrawExcessLamports
+//!     Accounts: [source, destination, authority, token_program]
+//!
+//! 28 = GetAccountDataSize (read: u8, expected_size: u64)
+//!     Accounts: [mint, token_program]
+//!
+//! 29 = AmountToUiAmount (read: u8, amount: u64, expected_ui_amount: [u8])
+//!     Accounts: [mint, token_program]
+//!
+//! 30 = UiAmountToAmount (read: u8, expected_amount: u64, ui_amount: [u8])
+//!     Accounts: [mint, token_program]
+//!
+//! The query operations (28-30) only CPI when `read` is 0. Otherwise they
+//! also read the token program's return data and fail with
+//! InvalidAccountData unless it holds the expected value.
+//!
+//! 31 = InitializeTransferFeeConfig (transfer_fee_basis_points: u16, maximum_fee: u64)
+//!     Accounts: [mint, transfer_fee_config_authority, withdraw_withheld_authority, token_program]
+//!
+//! 32 = TransferCheckedWithFee (amount: u64, decimals: u8, fee: u64)
+//!     Accounts: [source, mint, destination, authority, token_program]
+//!
+//! 33 = WithdrawWithheldTokensFromMint
+//!     Accounts: [mint, destination, withdraw_withheld_authority, token_program]
+//!
+//! 34 = WithdrawWithheldTokensFromAccounts
+//!     Accounts: [mint, destination, withdraw_withheld_authority, token_program, sources...]
+//!     Takes up to 32 sources.
+//!
+//! 35 = HarvestWithheldTokensToMint
+//!     Accounts: [mint, token_program, sources...]
+//!     Takes up to 32 sources.
+//!
+//! 36 = SetTransferFee (transfer_fee_basis_points: u16, maximum_fee: u64)
+//!     Accounts: [mint, transfer_fee_config_authority, token_program]
+//!
+//! 37 = TransferCheckedWithHook (amount: u64, decimals: u8)
+//!     Accounts: [source, mint, destination, authority, token_program,
+//!     hook_program, extra_account_metas, extra...]
+//!     Takes up to 16 extra accounts.
+//!
+//! 38-44 initialize a mint extension, then run InitializeMint2 with
+//! `authority` as both the mint and freeze authority. `authority` is also
+//! every authority (or delegate) the extension has, and the mint itself is
+//! the address the pointer extensions point to.
+//!     Accounts: [mint, authority, token_program]
+//!
+//! 38 = InitializeMintCloseAuthority (decimals: u8)
+//! 39 = InitializeNonTransferableMint (decimals: u8)
+//! 40 = InitializePermanentDelegate (decimals: u8)
+//! 41 = InitializeDefaultAccountState (decimals: u8, state: u8)
+//! 42 = InitializeInterestBearingMint (decimals: u8, rate: i16)
+//! 43 = InitializeMetadataPointer (decimals: u8)
+//! 44 = InitializeGroupPointer (decimals: u8)
+//!
+//! 45 is token-ops only (Memo).
+//!
+//! 46 = EnableRequiredMemoTransfers
+//!     Accounts: [account, owner, token_program]
+//!
+//! 47 = TransferCheckedWithMemo (amount: u64, decimals: u8, memo: [u8])
+//!     Accounts: [source, mint, destination, authority, token_program, memo_program]
+//!     Token-2022 only accepts a transfer into an account requiring memos
+//!     when the instruction processed right before it, at the same stack
+//!     height, is a memo. So the memo is a CPI from this program too, made
+//!     right before the TransferChecked one. The memo is the rest of the
+//!     instruction data.
+//!
+//! 48 = UpdateRate (rate: i16)
+//!     Accounts: [mint, rate_authority, token_program]
+//!
+//! 49 = UpdateMultiplier (multiplier: f64, effective_timestamp: i64)
+//!     Accounts: [mint, multiplier_authority, token_program]
+//!
+//! 50 = UpdateDefaultAccountState (state: u8)
+//!     Accounts: [mint, freeze_authority, token_program]
+//!
+//! 51 = Pause
+//!     Accounts: [mint, pause_authority, token_program]
+//!
+//! 52 = Resume
+//!     Accounts: [mint, pause_authority, token_program]
+
+pub mod instructions;
 
-use pinocchio::{account::AccountView, Address, ProgramResult};
+use instructions::{
+    AmountToUiAmount, CreateNativeMint, EnableRequiredMemoTransfers, GetAccountDataSize,
+    HarvestWithheldTokensToMint, InitializeMintExtension, InitializeTransferFeeConfig, Memo,
+    MintExtensionInit, SetExtensionAuthority, SetPaused, SetTransferFee, TransferCheckedWithFee,
+    TransferCheckedWithHook, UiAmountToAmount, UpdateDefaultAccountState, UpdateMultiplier,
+    UpdateRate, WithdrawExcessLamports, WithdrawWithheldTokensFromAccounts,
+    WithdrawWithheldTokensFromMint,
+};
+use pinocchio::{
+    account::AccountView, cpi::get_return_data, error::ProgramError, Address, ProgramResult,
+};
 use pinocchio_token_2022::instructions::{
-    Approve, Burn, CloseAccount, FreezeAccount, InitializeAccount, InitializeAccount2,
-    InitializeAccount3, InitializeMint, InitializeMint2, MintTo, Revoke, SetAuthority,
-    ThawAccount, Transfer, TransferChecked,
+    Approve, ApproveChecked, AuthorityType, Burn, BurnChecked, CloseAccount, FreezeAccount,
+    InitializeAccount, InitializeAccount2, InitializeAccount3, InitializeMint, InitializeMint2,
+    MintTo, MintToChecked, Revoke, SetAuthority, SyncNative, ThawAccount, Transfer,
+    TransferChecked,
 };
 
 #[cfg(feature = "bpf-entrypoint")]
@@ -281,17 +401,365 @@ pub fn process_instruction(
             } else {
                 None
             };
-            SetAuthority {
+            match authority_type_from_u8(authority_type)? {
+                DecodedAuthorityType::Base(authority_type) => SetAuthority {
+                    account: &accounts[0],
+                    authority: &accounts[1],
+                    authority_type,
+                    new_authority,
+                    token_program,
+                }
+                .invoke(),
+                DecodedAuthorityType::Extension(authority_type) => SetExtensionAuthority {
+                    account: &accounts[0],
+                    authority: &accounts[1],
+                    authority_type,
+                    new_authority,
+                    token_program,
+                }
+                .invoke(),
+            }
+        }
+        // MintToChecked
+        18 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let token_program = accounts[3].address();
+            MintToChecked {
+                mint: &accounts[0],
+                account: &accounts[1],
+                mint_authority: &accounts[2],
+                amount,
+                decimals,
+                token_program,
+            }
+            .invoke()
+        }
+        // BurnChecked
+        19 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let token_program = accounts[3].address();
+            BurnChecked {
                 account: &accounts[0],
+                mint: &accounts[1],
+                authority: &accounts[2],
+                amount,
+                decimals,
+                token_program,
+            }
+            .invoke()
+        }
+        // ApproveChecked
+        20 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let token_program = accounts[4].address();
+            ApproveChecked {
+                source: &accounts[0],
+                mint: &accounts[1],
+                delegate: &accounts[2],
+                authority: &accounts[3],
+                amount,
+                decimals,
+                token_program,
+            }
+            .invoke()
+        }
+        // SyncNative
+        25 => SyncNative {
+            native_token: &accounts[0],
+            token_program: accounts[1].address(),
+        }
+        .invoke(),
+        // CreateNativeMint
+        26 => CreateNativeMint {
+            payer: &accounts[0],
+            native_mint: &accounts[1],
+            system_program: &accounts[2],
+            token_program: accounts[3].address(),
+        }
+        .invoke(),
+        // WithdrawExcessLamports
+        27 => WithdrawExcessLamports {
+            source: &accounts[0],
+            destination: &accounts[1],
+            authority: &accounts[2],
+            token_program: accounts[3].address(),
+        }
+        .invoke(),
+        // GetAccountDataSize
+        28 => {
+            let read = instruction_data[1];
+            let token_program = accounts[1].address();
+            GetAccountDataSize {
+                mint: &accounts[0],
+                token_program,
+            }
+            .invoke()?;
+            check_return_data(token_program, read, &instruction_data[2..10])
+        }
+        // AmountToUiAmount
+        29 => {
+            let read = instruction_data[1];
+            let amount = u64::from_le_bytes(instruction_data[2..10].try_into().unwrap());
+            let token_program = accounts[1].address();
+            AmountToUiAmount {
+                mint: &accounts[0],
+                amount,
+                token_program,
+            }
+            .invoke()?;
+            check_return_data(token_program, read, &instruction_data[10..])
+        }
+        // UiAmountToAmount
+        30 => {
+            let read = instruction_data[1];
+            let token_program = accounts[1].address();
+            UiAmountToAmount {
+                mint: &accounts[0],
+                ui_amount: &instruction_data[10..],
+                token_program,
+            }
+            .invoke()?;
+            check_return_data(token_program, read, &instruction_data[2..10])
+        }
+        // InitializeTransferFeeConfig
+        31 => {
+            let transfer_fee_basis_points =
+                u16::from_le_bytes(instruction_data[1..3].try_into().unwrap());
+            let maximum_fee = u64::from_le_bytes(instruction_data[3..11].try_into().unwrap());
+            let token_program = accounts[3].address();
+            InitializeTransferFeeConfig {
+                mint: &accounts[0],
+                transfer_fee_config_authority: Some(accounts[1].address()),
+                withdraw_withheld_authority: Some(accounts[2].address()),
+                transfer_fee_basis_points,
+                maximum_fee,
+                token_program,
+            }
+            .invoke()
+        }
+        // TransferCheckedWithFee
+        32 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let fee = u64::from_le_bytes(instruction_data[10..18].try_into().unwrap());
+            let token_program = accounts[4].address();
+            TransferCheckedWithFee {
+                from: &accounts[0],
+                mint: &accounts[1],
+                to: &accounts[2],
+                authority: &accounts[3],
+                amount,
+                decimals,
+                fee,
+                token_program,
+            }
+            .invoke()
+        }
+        // WithdrawWithheldTokensFromMint
+        33 => WithdrawWithheldTokensFromMint {
+            mint: &accounts[0],
+            destination: &accounts[1],
+            authority: &accounts[2],
+            token_program: accounts[3].address(),
+        }
+        .invoke(),
+        // WithdrawWithheldTokensFromAccounts
+        34 => WithdrawWithheldTokensFromAccounts {
+            mint: &accounts[0],
+            destination: &accounts[1],
+            authority: &accounts[2],
+            sources: &accounts[4..],
+            token_program: accounts[3].address(),
+        }
+        .invoke(),
+        // HarvestWithheldTokensToMint
+        35 => HarvestWithheldTokensToMint {
+            mint: &accounts[0],
+            sources: &accounts[2..],
+            token_program: accounts[1].address(),
+        }
+        .invoke(),
+        // SetTransferFee
+        36 => {
+            let transfer_fee_basis_points =
+                u16::from_le_bytes(instruction_data[1..3].try_into().unwrap());
+            let maximum_fee = u64::from_le_bytes(instruction_data[3..11].try_into().unwrap());
+            let token_program = accounts[2].address();
+            SetTransferFee {
+                mint: &accounts[0],
                 authority: &accounts[1],
-                authority_type: unsafe { core::mem::transmute(authority_type) },
-                new_authority,
+                transfer_fee_basis_points,
+                maximum_fee,
+                token_program,
+            }
+            .invoke()
+        }
+        // TransferCheckedWithHook
+        37 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let token_program = accounts[4].address();
+            TransferCheckedWithHook {
+                from: &accounts[0],
+                mint: &accounts[1],
+                to: &accounts[2],
+                authority: &accounts[3],
+                hook_accounts: &accounts[5..],
+                amount,
+                decimals,
                 token_program,
             }
             .invoke()
         }
+        // Mint extension initializer, then InitializeMint2
+        38..=44 => {
+            let decimals = instruction_data[1];
+            let mint = &accounts[0];
+            let authority = accounts[1].address();
+            let token_program = accounts[2].address();
+            let extension = match operation {
+                38 => MintExtensionInit::MintCloseAuthority {
+                    close_authority: Some(authority),
+                },
+                39 => MintExtensionInit::NonTransferable,
+                40 => MintExtensionInit::PermanentDelegate {
+                    delegate: authority,
+                },
+                41 => MintExtensionInit::DefaultAccountState {
+                    state: instruction_data[2],
+                },
+                42 => MintExtensionInit::InterestBearing {
+                    rate_authority: Some(authority),
+                    rate: i16::from_le_bytes(instruction_data[2..4].try_into().unwrap()),
+                },
+                43 => MintExtensionInit::MetadataPointer {
+                    authority: Some(authority),
+                    metadata_address: Some(mint.address()),
+                },
+                _ => MintExtensionInit::GroupPointer {
+                    authority: Some(authority),
+                    group_address: Some(mint.address()),
+                },
+            };
+            InitializeMintExtension {
+                mint,
+                extension,
+                token_program,
+            }
+            .invoke()?;
+            InitializeMint2 {
+                mint,
+                decimals,
+                mint_authority: authority,
+                freeze_authority: Some(authority),
+                token_program,
+            }
+            .invoke()
+        }
+        // EnableRequiredMemoTransfers
+        46 => EnableRequiredMemoTransfers {
+            account: &accounts[0],
+            owner: &accounts[1],
+            token_program: accounts[2].address(),
+        }
+        .invoke(),
+        // TransferCheckedWithMemo
+        47 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            let token_program = accounts[4].address();
+            Memo {
+                program: &accounts[5],
+                signers: &[],
+                memo: &instruction_data[10..],
+            }
+            .invoke()?;
+            TransferChecked {
+                from: &accounts[0],
+                mint: &accounts[1],
+                to: &accounts[2],
+                authority: &accounts[3],
+                amount,
+                decimals,
+                token_program,
+            }
+            .invoke()
+        }
+        // UpdateRate
+        48 => UpdateRate {
+            mint: &accounts[0],
+            authority: &accounts[1],
+            rate: i16::from_le_bytes(instruction_data[1..3].try_into().unwrap()),
+            token_program: accounts[2].address(),
+        }
+        .invoke(),
+        // UpdateMultiplier
+        49 => UpdateMultiplier {
+            mint: &accounts[0],
+            authority: &accounts[1],
+            multiplier: f64::from_le_bytes(instruction_data[1..9].try_into().unwrap()),
+            effective_timestamp: i64::from_le_bytes(instruction_data[9..17].try_into().unwrap()),
+            token_program: accounts[2].address(),
+        }
+        .invoke(),
+        // UpdateDefaultAccountState
+        50 => UpdateDefaultAccountState {
+            mint: &accounts[0],
+            freeze_authority: &accounts[1],
+            state: instruction_data[1],
+            token_program: accounts[2].address(),
+        }
+        .invoke(),
+        // Pause, Resume
+        51 | 52 => SetPaused {
+            mint: &accounts[0],
+            authority: &accounts[1],
+            paused: operation == 51,
+            token_program: accounts[2].address(),
+        }
+        .invoke(),
         _ => Ok(()),
     }
 }
 
+/// SetAuthority `authority_type` byte after validation.
+enum DecodedAuthorityType {
+    /// One of the base SPL Token authority types.
+    Base(AuthorityType),
+    /// A Token-2022 extension authority type, forwarded as its raw byte.
+    Extension(u8),
+}
+
+/// Checks that `token_program` set the return data to `expected`, unless
+/// `read` is 0.
+fn check_return_data(token_program: &Address, read: u8, expected: &[u8]) -> ProgramResult {
+    if read == 0 {
+        return Ok(());
+    }
+    match get_return_data() {
+        Some(return_data)
+            if return_data.program_id() == token_program && return_data.as_slice() == expected =>
+        {
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Decodes a SetAuthority `authority_type` byte, rejecting values Token-2022
+/// does not define instead of transmuting them into an invalid enum.
+fn authority_type_from_u8(value: u8) -> Result<DecodedAuthorityType, ProgramError> {
+    match value {
+        0 => Ok(DecodedAuthorityType::Base(AuthorityType::MintTokens)),
+        1 => Ok(DecodedAuthorityType::Base(AuthorityType::FreezeAccount)),
+        2 => Ok(DecodedAuthorityType::Base(AuthorityType::AccountOwner)),
+        3 => Ok(DecodedAuthorityType::Base(AuthorityType::CloseAccount)),
+        4..=16 => Ok(DecodedAuthorityType::Extension(value)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
 pub const ID: [u8; 32] = [0x06; 32];
@@ -0,0 +1,438 @@
+//! CPIs token-ops makes that the pinocchio-token builders do not cover.
+//!
+//! Each builder mirrors the pinocchio-token ones: accounts and arguments are
+//! plain fields, and the instruction data is written into an uninitialized
+//! stack buffer right before the CPI.
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
+
+use pinocchio::{
+    account::AccountView,
+    cpi::{invoke_signed, invoke_signed_with_bounds, invoke_signed_with_slice, Signer},
+    error::ProgramError,
+    instruction::{InstructionAccount, InstructionView},
+    ProgramResult,
+};
+use pinocchio_token::instructions::MAX_MULTISIG_SIGNERS;
+
+const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
+
+const UNINIT_INSTRUCTION_ACCOUNT: MaybeUninit<InstructionAccount> =
+    MaybeUninit::<InstructionAccount>::uninit();
+
+const UNINIT_VIEW: MaybeUninit<&AccountView> = MaybeUninit::<&AccountView>::uninit();
+
+#[inline(always)]
+fn write_bytes(destination: &mut [MaybeUninit<u8>], source: &[u8]) {
+    let len = destination.len().min(source.len());
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            source.as_ptr(),
+            destination.as_mut_ptr() as *mut u8,
+            len,
+        );
+    }
+}
+
+/// Most remaining accounts `TransferWithRemaining` forwards.
+const MAX_REMAINING_ACCOUNTS: usize = 64;
+
+/// Transfer that also passes `remaining` to the token program, keeping each
+/// account's signer and writable flags.
+///
+/// ### Accounts:
+///   0. `[WRITE]` Sender account
+///   1. `[WRITE]` Recipient account
+///   2. `[SIGNER]` Authority account
+///   3. ..`3+N`. The N remaining accounts
+pub struct TransferWithRemaining<'a> {
+    /// Sender account.
+    pub from: &'a AccountView,
+    /// Recipient account.
+    pub to: &'a AccountView,
+    /// Authority account.
+    pub authority: &'a AccountView,
+    /// Accounts appended after the authority, at most `MAX_REMAINING_ACCOUNTS`.
+    pub remaining: &'a [AccountView],
+    /// Amount of micro-tokens to transfer.
+    pub amount: u64,
+}
+
+impl TransferWithRemaining<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.remaining.len() > MAX_REMAINING_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let account_count = 3 + self.remaining.len();
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; 3 + MAX_REMAINING_ACCOUNTS];
+        instruction_accounts[0].write(InstructionAccount::writable(self.from.address()));
+        instruction_accounts[1].write(InstructionAccount::writable(self.to.address()));
+        instruction_accounts[2].write(InstructionAccount::readonly_signer(
+            self.authority.address(),
+        ));
+        for (instruction_account, account) in
+            instruction_accounts[3..].iter_mut().zip(self.remaining)
+        {
+            instruction_account.write(InstructionAccount::from(account));
+        }
+
+        let mut account_views = [UNINIT_VIEW; 3 + MAX_REMAINING_ACCOUNTS];
+        account_views[0].write(self.from);
+        account_views[1].write(self.to);
+        account_views[2].write(self.authority);
+        for (account_view, account) in account_views[3..].iter_mut().zip(self.remaining) {
+            account_view.write(account);
+        }
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..9]: amount (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 9];
+
+        write_bytes(&mut instruction_data, &[3]);
+        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: &pinocchio_token::ID,
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 9) },
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_slice(&instruction, account_views, signers)
+    }
+}
+
+/// Most accounts `Forward` passes to the callee.
+const MAX_FORWARDED_ACCOUNTS: usize = 16;
+
+/// Invokes an arbitrary program with the given instruction data, passing
+/// `accounts` through with their signer and writable flags.
+///
+/// ### Accounts:
+///   0. ..`N`. The N accounts passed to the callee
+pub struct Forward<'a> {
+    /// Program to invoke.
+    pub program: &'a AccountView,
+    /// Accounts passed to the callee, at most `MAX_FORWARDED_ACCOUNTS`.
+    pub accounts: &'a [AccountView],
+    /// Instruction data passed to the callee.
+    pub data: &'a [u8],
+}
+
+impl Forward<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_count = self.accounts.len();
+        if account_count > MAX_FORWARDED_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; MAX_FORWARDED_ACCOUNTS];
+        let mut account_views = [UNINIT_VIEW; MAX_FORWARDED_ACCOUNTS];
+        for ((instruction_account, account_view), account) in instruction_accounts
+            .iter_mut()
+            .zip(account_views.iter_mut())
+            .zip(self.accounts)
+        {
+            instruction_account.write(InstructionAccount::from(account));
+            account_view.write(account);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.program.address(),
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: self.data,
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_bounds::<MAX_FORWARDED_ACCOUNTS>(&instruction, account_views, signers)
+    }
+}
+
+/// Most signer accounts `Memo` passes to the memo program.
+const MAX_MEMO_SIGNERS: usize = 16;
+
+/// Logs `memo` through the SPL Memo program, which also checks that every
+/// account passed to it signed.
+///
+/// ### Accounts:
+///   0. ..`N`. `[SIGNER]` The N memo signers
+pub struct Memo<'a> {
+    /// Memo program.
+    pub program: &'a AccountView,
+    /// Memo signer accounts, at most `MAX_MEMO_SIGNERS`.
+    pub signers: &'a [AccountView],
+    /// Memo, which the memo program requires to be UTF-8.
+    pub memo: &'a [u8],
+}
+
+impl Memo<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_count = self.signers.len();
+        if account_count > MAX_MEMO_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; MAX_MEMO_SIGNERS];
+        for (instruction_account, signer) in instruction_accounts.iter_mut().zip(self.signers) {
+            instruction_account.write(InstructionAccount::readonly_signer(signer.address()));
+        }
+
+        let mut account_views = [UNINIT_VIEW; MAX_MEMO_SIGNERS];
+        for (account_view, signer) in account_views.iter_mut().zip(self.signers) {
+            account_view.write(signer);
+        }
+
+        let instruction = InstructionView {
+            program_id: self.program.address(),
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: self.memo,
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_bounds::<MAX_MEMO_SIGNERS>(&instruction, account_views, signers)
+    }
+}
+
+/// Largest instruction data `MultisigSigned` supports: a discriminator plus
+/// an amount and decimals.
+const MAX_MULTISIG_SIGNED_DATA: usize = 10;
+
+/// Most accounts `MultisigSigned` passes on: the underlying instruction's
+/// accounts, at most four, plus the multisig signers.
+const MAX_MULTISIG_SIGNED_ACCOUNTS: usize = 4 + MAX_MULTISIG_SIGNERS;
+
+/// Token instruction whose authority is a multisig. The instruction's own
+/// accounts come first, ending with the multisig in the authority slot (not
+/// signing), and the multisig signers are appended as read-only signers.
+///
+/// ### Accounts:
+///   0. ..`N+M`. The N accounts of the underlying instruction, followed by
+///      the M `[SIGNER]` multisig signers
+pub struct MultisigSigned<'a, 'b> {
+    /// Accounts of the underlying instruction.
+    pub accounts: &'a [AccountView],
+    /// Whether each of `accounts` is writable.
+    pub writable: &'b [bool],
+    /// Multisig signer accounts.
+    pub signers: &'a [AccountView],
+    /// Token instruction discriminator.
+    pub discriminator: u8,
+    /// Token instruction arguments following the discriminator.
+    pub args: &'b [u8],
+}
+
+impl MultisigSigned<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_count = self.accounts.len() + self.signers.len();
+        if self.accounts.len() != self.writable.len()
+            || self.args.len() >= MAX_MULTISIG_SIGNED_DATA
+            || account_count > MAX_MULTISIG_SIGNED_ACCOUNTS
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [UNINIT_INSTRUCTION_ACCOUNT; MAX_MULTISIG_SIGNED_ACCOUNTS];
+        let mut account_views = [UNINIT_VIEW; MAX_MULTISIG_SIGNED_ACCOUNTS];
+        // The instruction's own accounts, then the multisig signers
+        let flagged_accounts = self
+            .accounts
+            .iter()
+            .zip(self.writable)
+            .map(|(account, &writable)| {
+                (account, InstructionAccount::new(account.address(), writable, false))
+            })
+            .chain(self.signers.iter().map(|signer| {
+                (signer, InstructionAccount::readonly_signer(signer.address()))
+            }));
+        for ((instruction_account, account_view), (account, flags)) in instruction_accounts
+            .iter_mut()
+            .zip(account_views.iter_mut())
+            .zip(flagged_accounts)
+        {
+            instruction_account.write(flags);
+            account_view.write(account);
+        }
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..]: arguments of the underlying instruction
+        let mut instruction_data = [UNINIT_BYTE; MAX_MULTISIG_SIGNED_DATA];
+        let length = 1 + self.args.len();
+
+        write_bytes(&mut instruction_data, &[self.discriminator]);
+        write_bytes(&mut instruction_data[1..], self.args);
+
+        let instruction = InstructionView {
+            program_id: &pinocchio_token::ID,
+            // SAFETY: the first `account_count` entries were initialized above.
+            accounts: unsafe {
+                from_raw_parts(instruction_accounts.as_ptr() as _, account_count)
+            },
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        // SAFETY: the first `account_count` entries were initialized above.
+        let account_views =
+            unsafe { from_raw_parts(account_views.as_ptr() as _, account_count) };
+        invoke_signed_with_bounds::<MAX_MULTISIG_SIGNED_ACCOUNTS>(
+            &instruction,
+            account_views,
+            signers,
+        )
+    }
+}
+
+/// Maximum length of the UI amount string `UiAmountToAmount` accepts.
+pub const MAX_UI_AMOUNT_LEN: usize = 128;
+
+/// Gets the size of a token account for `mint`. The token program sets it as
+/// return data (8 bytes, u64).
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct GetAccountDataSize<'a> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+}
+
+impl GetAccountDataSize<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        let instruction = InstructionView {
+            program_id: &pinocchio_token::ID,
+            accounts: &instruction_accounts,
+            data: &[21],
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Converts an amount of tokens into a UI amount string using the mint's
+/// decimals. The token program sets the string as return data.
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct AmountToUiAmount<'a> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// Amount of micro-tokens to convert.
+    pub amount: u64,
+}
+
+impl AmountToUiAmount<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..9]: amount (8 bytes, u64)
+        let mut instruction_data = [UNINIT_BYTE; 9];
+
+        write_bytes(&mut instruction_data, &[23]);
+        write_bytes(&mut instruction_data[1..9], &self.amount.to_le_bytes());
+
+        let instruction = InstructionView {
+            program_id: &pinocchio_token::ID,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, 9) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
+
+/// Converts a UI amount string into an amount of tokens using the mint's
+/// decimals. The token program sets the amount as return data (8 bytes, u64).
+///
+/// ### Accounts:
+///   0. `[]` The mint to calculate for.
+pub struct UiAmountToAmount<'a, 'b> {
+    /// Mint account.
+    pub mint: &'a AccountView,
+    /// UTF-8 UI amount, at most `MAX_UI_AMOUNT_LEN` bytes.
+    pub ui_amount: &'b [u8],
+}
+
+impl UiAmountToAmount<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.ui_amount.len() > MAX_UI_AMOUNT_LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let instruction_accounts: [InstructionAccount; 1] =
+            [InstructionAccount::readonly(self.mint.address())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1..]: ui_amount (up to MAX_UI_AMOUNT_LEN bytes, UTF-8)
+        let mut instruction_data = [UNINIT_BYTE; 1 + MAX_UI_AMOUNT_LEN];
+        let length = 1 + self.ui_amount.len();
+
+        write_bytes(&mut instruction_data, &[24]);
+        write_bytes(&mut instruction_data[1..], self.ui_amount);
+
+        let instruction = InstructionView {
+            program_id: &pinocchio_token::ID,
+            accounts: &instruction_accounts,
+            data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, length) },
+        };
+
+        invoke_signed(&instruction, &[self.mint], signers)
+    }
+}
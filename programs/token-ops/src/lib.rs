@@ -52,12 +52,105 @@
 //!
 //! 14 = SetAuthority (authority_type: u8, has_new_authority: u8)
 //!     Accounts: [account, authority, new_authority?, token_program]
+//!     authority_type must be 0-3 (MintTokens, FreezeAccount, AccountOwner,
+//!     CloseAccount); anything else fails with InvalidInstructionData.
+//!
+//! 15 = Script (step_count: u8, steps)
+//!     Accounts: every account referenced by the steps
+//!     Runs the operations above in order within one instruction. Each step is
+//!     laid out as:
+//!     - account_count: u8
+//!     - account_indices: [u8; account_count], indices into the accounts
+//!     - data_len: u8
+//!     - data: [u8; data_len], instruction data of a single operation
+//!       (discriminator and its arguments); nested scripts are rejected
+//!
+//! 16 = TransferWithRemaining (amount: u64)
+//!     Accounts: [source, destination, authority, token_program, remaining...]
+//!     Like Transfer, but every remaining account (at most 64) is forwarded
+//!     to the CPI.
+//!
+//! 17 = Forward (data: [u8], the rest of the instruction data)
+//!     Accounts: [callee_program, callee_accounts...]
+//!     Invokes callee_program with `data` and up to 16 callee accounts.
+//!
+//! 18 = MintToChecked (amount: u64, decimals: u8)
+//!     Accounts: [mint, destination, mint_authority, token_program]
+//!
+//! 19 = BurnChecked (amount: u64, decimals: u8)
+//!     Accounts: [source, mint, authority, token_program]
+//!
+//! 20 = ApproveChecked (amount: u64, decimals: u8)
+//!     Accounts: [source, mint, delegate, authority, token_program]
+//!
+//! 21 = InitializeMultisig (m: u8)
+//!     Accounts: [multisig, rent_sysvar, token_program, signers...]
+//!
+//! 22 = InitializeMultisig2 (m: u8)
+//!     Accounts: [multisig, token_program, signers...]
+//!
+//! 23 = Multisig (operation: u8, operation arguments)
+//!     Accounts: [operation accounts..., token_program, signers...]
+//!     Runs Transfer (0), MintTo (1), Burn (2), Approve (3) or
+//!     TransferChecked (8) with a multisig as the authority. The operation
+//!     accounts and arguments are the same as for the plain operation, with
+//!     the multisig in the authority slot; the signers follow token_program.
+//!
+//! 24 = PdaSigned (seed_count: u8, seed_len: u8, bump: u8, operation data)
+//!     Accounts: the accounts of the wrapped operation
+//!     Runs any other operation with its CPIs signed by the token-ops PDA
+//!     derived from `seed_count` seeds of `seed_len` bytes (seed `i` is
+//!     `seed_len` bytes of value `i`) plus `bump`. The PDA goes in the
+//!     wrapped operation's authority slot, as a non-signer.
+//!
+//! 25 = SyncNative
+//!     Accounts: [native_token, token_program]
+//!
+//! 26-27 are token-ops-2022 only (CreateNativeMint, WithdrawExcessLamports).
+//!
+//! 28 = GetAccountDataSize (read: u8, expected_size: u64)
+//!     Accounts: [mint, token_program]
+//!
+//! 29 = AmountToUiAmount (read: u8, amount: u64, expected_ui_amount: [u8])
+//!     Accounts: [mint, token_program]
+//!
+//! 30 = UiAmountToAmount (read: u8, expected_amount: u64, ui_amount: [u8])
+//!     Accounts: [mint, token_program]
+//!
+//! The query operations (28-30) only CPI when `read` is 0. Otherwise they
+//! also read the token program's return data and fail with
+//! InvalidAccountData unless it holds the expected value.
+//!
+//! 31-44 are token-ops-2022 only (Token-2022 extension instructions).
+//!
+//! 45 = Memo (memo: [u8], the rest of the instruction data)
+//!     Accounts: [memo_program, signers...]
+//!     Takes up to 16 signers.
+//!
+//! 46-52 are token-ops-2022 only (EnableRequiredMemoTransfers,
+//! TransferCheckedWithMemo, UpdateRate, UpdateMultiplier,
+//! UpdateDefaultAccountState, Pause, Resume).
+
+pub mod instructions;
+
+use core::{mem::MaybeUninit, slice::from_raw_parts};
 
-use pinocchio::{account::AccountView, Address, ProgramResult};
+use instructions::{
+    AmountToUiAmount, Forward, GetAccountDataSize, Memo, MultisigSigned, TransferWithRemaining,
+    UiAmountToAmount,
+};
+use pinocchio::{
+    account::AccountView,
+    address::{MAX_SEEDS, MAX_SEED_LEN},
+    cpi::{get_return_data, Seed, Signer},
+    error::ProgramError,
+    Address, ProgramResult,
+};
 use pinocchio_token::instructions::{
-    Approve, Burn, CloseAccount, FreezeAccount, InitializeAccount, InitializeAccount2,
-    InitializeAccount3, InitializeMint, InitializeMint2, MintTo, Revoke, SetAuthority,
-    ThawAccount, Transfer, TransferChecked,
+    Approve, ApproveChecked, AuthorityType, Burn, BurnChecked, CloseAccount, FreezeAccount,
+    InitializeAccount, InitializeAccount2, InitializeAccount3, InitializeMint, InitializeMint2,
+    InitializeMultisig, InitializeMultisig2, MintTo, MintToChecked, Revoke, SetAuthority,
+    SyncNative, ThawAccount, Transfer, TransferChecked, MAX_MULTISIG_SIGNERS,
 };
 
 #[cfg(feature = "bpf-entrypoint")]
@@ -75,10 +168,41 @@ mod entrypoint {
     }
 }
 
+/// Discriminator of the Script operation
+const SCRIPT: u8 = 15;
+
+/// Maximum number of accounts a single script step can reference
+const MAX_STEP_ACCOUNTS: usize = 16;
+
+/// Discriminator of the PdaSigned operation
+const PDA_SIGNED: u8 = 24;
+
+/// Bytes of the PdaSigned seeds: seed `i` is `seed_len` bytes of value `i`
+const SEED_BYTES: [[u8; MAX_SEED_LEN]; MAX_SEEDS - 1] = {
+    let mut seeds = [[0u8; MAX_SEED_LEN]; MAX_SEEDS - 1];
+    let mut index = 0;
+    while index < seeds.len() {
+        seeds[index] = [index as u8; MAX_SEED_LEN];
+        index += 1;
+    }
+    seeds
+};
+
 pub fn process_instruction(
-    _program_id: &Address,
+    program_id: &Address,
     accounts: &[AccountView],
     instruction_data: &[u8],
+) -> ProgramResult {
+    process_operation(program_id, accounts, instruction_data, &[])
+}
+
+/// Runs the operation encoded in `instruction_data`, signing its CPIs with
+/// `signers`.
+fn process_operation(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+    signers: &[Signer],
 ) -> ProgramResult {
     let operation = instruction_data[0];
 
@@ -92,7 +216,7 @@ pub fn process_instruction(
                 authority: &accounts[2],
                 amount,
             }
-            .invoke()
+            .invoke_signed(signers)
         }
         // MintTo
         1 => {
@@ -103,7 +227,7 @@ pub fn process_instruction(
                 mint_authority: &accounts[2],
                 amount,
             }
-            .invoke()
+            .invoke_signed(signers)
         }
         // Burn
         2 => {
@@ -114,7 +238,7 @@ pub fn process_instruction(
                 authority: &accounts[2],
                 amount,
             }
-            .invoke()
+            .invoke_signed(signers)
         }
         // Approve
         3 => {
@@ -125,35 +249,35 @@ pub fn process_instruction(
                 authority: &accounts[2],
                 amount,
             }
-            .invoke()
+            .invoke_signed(signers)
         }
         // Revoke
         4 => Revoke {
             source: &accounts[0],
             authority: &accounts[1],
         }
-        .invoke(),
+        .invoke_signed(signers),
         // CloseAccount
         5 => CloseAccount {
             account: &accounts[0],
             destination: &accounts[1],
             authority: &accounts[2],
         }
-        .invoke(),
+        .invoke_signed(signers),
         // FreezeAccount
         6 => FreezeAccount {
             account: &accounts[0],
             mint: &accounts[1],
             freeze_authority: &accounts[2],
         }
-        .invoke(),
+        .invoke_signed(signers),
         // ThawAccount
         7 => ThawAccount {
             account: &accounts[0],
             mint: &accounts[1],
             freeze_authority: &accounts[2],
         }
-        .invoke(),
+        .invoke_signed(signers),
         // TransferChecked
         8 => {
             let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
@@ -166,7 +290,7 @@ pub fn process_instruction(
                 amount,
                 decimals,
             }
-            .invoke()
+            .invoke_signed(signers)
         }
         // InitializeMint
         9 => {
@@ -228,7 +352,7 @@ pub fn process_instruction(
         .invoke(),
         // SetAuthority
         14 => {
-            let authority_type = instruction_data[1];
+            let authority_type = authority_type_from_u8(instruction_data[1])?;
             let has_new_authority = instruction_data[2] != 0;
             let new_authority = if has_new_authority {
                 Some(accounts[2].address())
@@ -238,13 +362,302 @@ pub fn process_instruction(
             SetAuthority {
                 account: &accounts[0],
                 authority: &accounts[1],
-                authority_type: unsafe { core::mem::transmute(authority_type) },
+                authority_type,
                 new_authority,
             }
+            .invoke_signed(signers)
+        }
+        // Script
+        SCRIPT => {
+            let step_count = instruction_data[1];
+            let mut script = &instruction_data[2..];
+            for _ in 0..step_count {
+                script = process_step(program_id, accounts, script, signers)?;
+            }
+            Ok(())
+        }
+        // TransferWithRemaining
+        16 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            TransferWithRemaining {
+                from: &accounts[0],
+                to: &accounts[1],
+                authority: &accounts[2],
+                remaining: &accounts[4..],
+                amount,
+            }
+            .invoke_signed(signers)
+        }
+        // Forward
+        17 => Forward {
+            program: &accounts[0],
+            accounts: &accounts[1..],
+            data: &instruction_data[1..],
+        }
+        .invoke_signed(signers),
+        // MintToChecked
+        18 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            MintToChecked {
+                mint: &accounts[0],
+                account: &accounts[1],
+                mint_authority: &accounts[2],
+                amount,
+                decimals,
+            }
+            .invoke_signed(signers)
+        }
+        // BurnChecked
+        19 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            BurnChecked {
+                account: &accounts[0],
+                mint: &accounts[1],
+                authority: &accounts[2],
+                amount,
+                decimals,
+            }
+            .invoke_signed(signers)
+        }
+        // ApproveChecked
+        20 => {
+            let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+            let decimals = instruction_data[9];
+            ApproveChecked {
+                source: &accounts[0],
+                mint: &accounts[1],
+                delegate: &accounts[2],
+                authority: &accounts[3],
+                amount,
+                decimals,
+            }
+            .invoke_signed(signers)
+        }
+        // InitializeMultisig
+        21 => {
+            let m = instruction_data[1];
+            let mut signer_buffer = [UNINIT_SIGNER; MAX_MULTISIG_SIGNERS];
+            let signers = multisig_signers(&accounts[3..], &mut signer_buffer)?;
+            InitializeMultisig {
+                multisig: &accounts[0],
+                rent_sysvar: &accounts[1],
+                signers,
+                m,
+            }
+            .invoke()
+        }
+        // InitializeMultisig2
+        22 => {
+            let m = instruction_data[1];
+            let mut signer_buffer = [UNINIT_SIGNER; MAX_MULTISIG_SIGNERS];
+            let signers = multisig_signers(&accounts[2..], &mut signer_buffer)?;
+            InitializeMultisig2 {
+                multisig: &accounts[0],
+                signers,
+                m,
+            }
             .invoke()
         }
+        // Multisig
+        23 => {
+            let (discriminator, writable) = multisig_layout(instruction_data[1])?;
+            let (accounts, multisig_signers) = accounts.split_at(writable.len());
+            MultisigSigned {
+                accounts,
+                writable,
+                // Skip the token program account
+                signers: &multisig_signers[1..],
+                discriminator,
+                args: &instruction_data[2..],
+            }
+            .invoke_signed(signers)
+        }
+        // PdaSigned
+        PDA_SIGNED => process_pda_signed(program_id, accounts, instruction_data),
+        // SyncNative
+        25 => SyncNative {
+            native_token: &accounts[0],
+        }
+        .invoke(),
+        // GetAccountDataSize
+        28 => {
+            let read = instruction_data[1];
+            GetAccountDataSize { mint: &accounts[0] }.invoke()?;
+            check_return_data(&pinocchio_token::ID, read, &instruction_data[2..10])
+        }
+        // AmountToUiAmount
+        29 => {
+            let read = instruction_data[1];
+            let amount = u64::from_le_bytes(instruction_data[2..10].try_into().unwrap());
+            AmountToUiAmount {
+                mint: &accounts[0],
+                amount,
+            }
+            .invoke()?;
+            check_return_data(&pinocchio_token::ID, read, &instruction_data[10..])
+        }
+        // UiAmountToAmount
+        30 => {
+            let read = instruction_data[1];
+            UiAmountToAmount {
+                mint: &accounts[0],
+                ui_amount: &instruction_data[10..],
+            }
+            .invoke()?;
+            check_return_data(&pinocchio_token::ID, read, &instruction_data[2..10])
+        }
+        // Memo
+        45 => Memo {
+            program: &accounts[0],
+            signers: &accounts[1..],
+            memo: &instruction_data[1..],
+        }
+        .invoke_signed(signers),
         _ => Ok(()),
     }
 }
 
+/// Runs the operation that follows the seed configuration in
+/// `instruction_data`, signed by the token-ops PDA derived from those seeds.
+fn process_pda_signed(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let seed_count = instruction_data[1] as usize;
+    let seed_len = instruction_data[2] as usize;
+    let bump = [instruction_data[3]];
+    let operation = &instruction_data[4..];
+    // One seed slot is taken by the bump
+    if seed_count >= MAX_SEEDS
+        || seed_len > MAX_SEED_LEN
+        || operation.is_empty()
+        || operation[0] == PDA_SIGNED
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    const UNINIT_SEED: MaybeUninit<Seed> = MaybeUninit::<Seed>::uninit();
+    let mut seeds = [UNINIT_SEED; MAX_SEEDS];
+    for (index, seed) in seeds[..seed_count].iter_mut().enumerate() {
+        seed.write(Seed::from(&SEED_BYTES[index][..seed_len]));
+    }
+    seeds[seed_count].write(Seed::from(&bump));
+    // SAFETY: the first `seed_count + 1` entries were initialized above.
+    let seeds = unsafe { from_raw_parts(seeds.as_ptr() as *const Seed, seed_count + 1) };
+
+    process_operation(program_id, accounts, operation, &[Signer::from(seeds)])
+}
+
+const UNINIT_SIGNER: MaybeUninit<&AccountView> = MaybeUninit::<&AccountView>::uninit();
+
+/// Collects the multisig `signers` into `buffer`, rejecting more than a
+/// multisig can hold.
+fn multisig_signers<'a, 'b>(
+    signers: &'a [AccountView],
+    buffer: &'b mut [MaybeUninit<&'a AccountView>; MAX_MULTISIG_SIGNERS],
+) -> Result<&'b [&'a AccountView], ProgramError> {
+    if signers.len() > MAX_MULTISIG_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+    for (slot, signer) in buffer.iter_mut().zip(signers) {
+        slot.write(signer);
+    }
+    // SAFETY: the first `signers.len()` entries were initialized above.
+    Ok(unsafe { from_raw_parts(buffer.as_ptr() as *const &AccountView, signers.len()) })
+}
+
+/// Token instruction discriminator and account writability of each operation
+/// the Multisig operation supports; the multisig authority is always last.
+fn multisig_layout(operation: u8) -> Result<(u8, &'static [bool]), ProgramError> {
+    match operation {
+        // Transfer: [source, destination, multisig]
+        0 => Ok((3, &[true, true, false])),
+        // MintTo: [mint, destination, multisig]
+        1 => Ok((7, &[true, true, false])),
+        // Burn: [source, mint, multisig]
+        2 => Ok((8, &[true, true, false])),
+        // Approve: [source, delegate, multisig]
+        3 => Ok((4, &[true, false, false])),
+        // TransferChecked: [source, mint, destination, multisig]
+        8 => Ok((12, &[true, false, true, false])),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Executes the script step at the start of `script`, signing its CPIs with
+/// `signers`, and returns the steps that follow it.
+fn process_step<'a>(
+    program_id: &Address,
+    accounts: &[AccountView],
+    script: &'a [u8],
+    signers: &[Signer],
+) -> Result<&'a [u8], ProgramError> {
+    let (&account_count, script) = script
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let account_count = account_count as usize;
+    if account_count > MAX_STEP_ACCOUNTS || script.len() < account_count {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (account_indices, script) = script.split_at(account_count);
+
+    let (&data_len, script) = script
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let data_len = data_len as usize;
+    if data_len == 0 || script.len() < data_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (data, script) = script.split_at(data_len);
+    if data[0] == SCRIPT {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    const UNINIT_ACCOUNT: MaybeUninit<AccountView> = MaybeUninit::<AccountView>::uninit();
+    let mut step_accounts = [UNINIT_ACCOUNT; MAX_STEP_ACCOUNTS];
+    for (step_account, &index) in step_accounts.iter_mut().zip(account_indices) {
+        let account = accounts
+            .get(index as usize)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        step_account.write(account.clone());
+    }
+    // SAFETY: the first `account_count` entries were initialized above.
+    let step_accounts =
+        unsafe { from_raw_parts(step_accounts.as_ptr() as *const AccountView, account_count) };
+
+    process_operation(program_id, step_accounts, data, signers)?;
+    Ok(script)
+}
+
+/// Checks that `token_program` set the return data to `expected`, unless
+/// `read` is 0.
+fn check_return_data(token_program: &Address, read: u8, expected: &[u8]) -> ProgramResult {
+    if read == 0 {
+        return Ok(());
+    }
+    match get_return_data() {
+        Some(return_data)
+            if return_data.program_id() == token_program && return_data.as_slice() == expected =>
+        {
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Decodes a SetAuthority `authority_type` byte, rejecting values SPL Token
+/// does not define instead of transmuting them into an invalid enum.
+fn authority_type_from_u8(value: u8) -> Result<AuthorityType, ProgramError> {
+    match value {
+        0 => Ok(AuthorityType::MintTokens),
+        1 => Ok(AuthorityType::FreezeAccount),
+        2 => Ok(AuthorityType::AccountOwner),
+        3 => Ok(AuthorityType::CloseAccount),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
 pub const ID: [u8; 32] = [0x05; 32];
@@ -0,0 +1,118 @@
+//! Minimal SPL transfer-hook program
+//!
+//! Token-2022 invokes this program's Execute instruction on every
+//! TransferChecked of a mint whose TransferHook extension names it, so the
+//! benchmarks can measure hook resolution and the nested CPI.
+//!
+//! Instruction format (spl-transfer-hook-interface):
+//! - Bytes 0..8: Instruction discriminator
+//! - Remaining bytes: Instruction-specific data
+//!
+//! Instructions:
+//! Execute (amount: u64)
+//!     Accounts: [source, mint, destination, authority, extra_account_metas, extra...]
+//!
+//! `extra_account_metas` is the PDA at ["extra-account-metas", mint], holding
+//! an ExtraAccountMetaList TLV entry for Execute. Token-2022 derives its
+//! address and resolves the extra accounts from it, so Execute only checks
+//! the account belongs to this program and that every listed account was
+//! passed, in order. Only fixed-address extra accounts are supported.
+//!
+//! InitializeExtraAccountMetaList is not implemented: the benchmarks write
+//! the PDA's state directly, like every other fixture.
+
+use pinocchio::{account::AccountView, error::ProgramError, Address, ProgramResult};
+
+#[cfg(feature = "bpf-entrypoint")]
+mod entrypoint {
+    use pinocchio::{account::AccountView, entrypoint, Address, ProgramResult};
+
+    entrypoint!(process_instruction);
+
+    fn process_instruction(
+        program_id: &Address,
+        accounts: &[AccountView],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        super::process_instruction(program_id, accounts, instruction_data)
+    }
+}
+
+/// Discriminator of Execute: the first 8 bytes of
+/// sha256("spl-transfer-hook-interface:execute").
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+/// Accounts Execute takes before the extra accounts.
+const EXECUTE_ACCOUNTS: usize = 5;
+
+/// Length of the TLV header: discriminator (8 bytes) and value length (4
+/// bytes, u32).
+const TLV_HEADER_LEN: usize = 12;
+
+/// Length of an ExtraAccountMeta: discriminator (1 byte, u8), address config
+/// (32 bytes), is_signer (1 byte, bool) and is_writable (1 byte, bool).
+const EXTRA_ACCOUNT_META_LEN: usize = 35;
+
+/// ExtraAccountMeta discriminator of a fixed address.
+const FIXED_ADDRESS: u8 = 0;
+
+pub fn process_instruction(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (discriminator, data) = instruction_data
+        .split_at_checked(8)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    // Execute is the only instruction; its data is the amount (8 bytes, u64)
+    if discriminator != EXECUTE_DISCRIMINATOR || data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if accounts.len() < EXECUTE_ACCOUNTS {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    execute(program_id, accounts)
+}
+
+/// Checks the extra accounts Token-2022 passed against the list in the
+/// extra-account-metas PDA.
+fn execute(program_id: &Address, accounts: &[AccountView]) -> ProgramResult {
+    let extra_account_metas = &accounts[EXECUTE_ACCOUNTS - 1];
+    if !extra_account_metas.owned_by(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let data = extra_account_metas.try_borrow()?;
+    // Layout:
+    // -  [0..8]: TLV discriminator (Execute's)
+    // -  [8..12]: TLV value length (4 bytes, u32)
+    // -  [12..16]: extra account count (4 bytes, u32)
+    // -  [16..]: extra account metas
+    let (header, metas) = data
+        .split_at_checked(TLV_HEADER_LEN + 4)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if header[..8] != EXECUTE_DISCRIMINATOR {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let count = u32::from_le_bytes(header[TLV_HEADER_LEN..].try_into().unwrap()) as usize;
+
+    let extra_accounts = &accounts[EXECUTE_ACCOUNTS..];
+    if extra_accounts.len() < count || metas.len() < count * EXTRA_ACCOUNT_META_LEN {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for (meta, account) in metas
+        .chunks_exact(EXTRA_ACCOUNT_META_LEN)
+        .zip(extra_accounts)
+        .take(count)
+    {
+        if meta[0] != FIXED_ADDRESS || &meta[1..33] != account.address().as_ref() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
+    Ok(())
+}
+
+pub const ID: [u8; 32] = [0x07; 32];
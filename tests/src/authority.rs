@@ -0,0 +1,237 @@
+//! Alternate-authority benchmarks: Transfer and Burn signed by a delegate
+//! and CloseAccount signed by a close authority, next to the same ops signed
+//! by the account owner, through both token-ops programs.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, TokenAccountBuilder},
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// Amount moved or burned by every case
+const AMOUNT: u64 = 1000;
+
+/// Supply of the fixture mint, all of it held by the source account
+const SUPPLY: u64 = 1_000_000_000;
+
+/// Allowance of the delegate, well above `AMOUNT`
+const DELEGATED_AMOUNT: u64 = 1_000_000;
+
+#[derive(Clone, Copy, Debug)]
+enum AlternateOp {
+    Transfer,
+    TransferChecked,
+    Burn,
+    BurnChecked,
+    CloseAccount,
+}
+
+impl AlternateOp {
+    /// Which authority signs instead of the owner
+    fn alternate_authority(self) -> &'static str {
+        match self {
+            AlternateOp::CloseAccount => "close authority",
+            _ => "delegate",
+        }
+    }
+}
+
+/// Runs `op` on a token account that has a delegate and, unless the owner
+/// closes it, a close authority, signed by the owner or by the alternate
+/// authority for `op`
+fn run_alternate_benchmark(
+    token_ops_bytes: &[u8],
+    backend: Backend,
+    op: AlternateOp,
+    use_alternate: bool,
+) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let owner = Keypair::new();
+    let delegate = Keypair::new();
+    let close_authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&owner.pubkey()).supply(SUPPLY).build();
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    // CloseAccount needs an empty account; everything else moves tokens out of it
+    let amount = match op {
+        AlternateOp::CloseAccount => 0,
+        _ => SUPPLY,
+    };
+    let source_token = Pubkey::new_unique();
+    let mut source_builder = TokenAccountBuilder::new(&mint, &owner.pubkey())
+        .amount(amount)
+        .delegate(&delegate.pubkey(), DELEGATED_AMOUNT);
+    // Both programs check CloseAccount against the close authority when one
+    // is set, so the owner can only close an account without one
+    if !matches!(op, AlternateOp::CloseAccount) || use_alternate {
+        source_builder = source_builder.close_authority(&close_authority.pubkey());
+    }
+    let source_data = source_builder.build();
+    svm.set_account(
+        source_token,
+        fixture_account(backend.token_program(), source_data),
+    )
+    .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &owner.pubkey()).build();
+    svm.set_account(
+        dest_token,
+        fixture_account(backend.token_program(), dest_data),
+    )
+    .unwrap();
+
+    let signer = match (op, use_alternate) {
+        (_, false) => &owner,
+        (AlternateOp::CloseAccount, true) => &close_authority,
+        (_, true) => &delegate,
+    };
+    let authority_meta = AccountMeta::new_readonly(signer.pubkey(), true);
+    let token_program_meta = AccountMeta::new_readonly(backend.token_program(), false);
+
+    let (accounts, data) = match op {
+        AlternateOp::Transfer => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[0u8][..], &AMOUNT.to_le_bytes()].concat(),
+        ),
+        AlternateOp::TransferChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[8u8][..], &AMOUNT.to_le_bytes(), &[9]].concat(),
+        ),
+        AlternateOp::Burn => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[2u8][..], &AMOUNT.to_le_bytes()].concat(),
+        ),
+        AlternateOp::BurnChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[19u8][..], &AMOUNT.to_le_bytes(), &[9]].concat(),
+        ),
+        AlternateOp::CloseAccount => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(payer.pubkey(), false),
+                authority_meta,
+                token_program_meta,
+            ],
+            vec![5u8],
+        ),
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[signer]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for {:?} (alternate: {}): {:?}",
+            op, use_alternate, e
+        ),
+    };
+
+    // A delegate spends from its allowance; the owner leaves it untouched
+    let delegated_amount = if use_alternate {
+        DELEGATED_AMOUNT - AMOUNT
+    } else {
+        DELEGATED_AMOUNT
+    };
+    let expected = match op {
+        AlternateOp::Transfer | AlternateOp::TransferChecked => vec![
+            Expect::Amount(source_token, SUPPLY - AMOUNT),
+            Expect::Amount(dest_token, AMOUNT),
+            Expect::Delegate(source_token, Some((delegate.pubkey(), delegated_amount))),
+        ],
+        AlternateOp::Burn | AlternateOp::BurnChecked => vec![
+            Expect::Amount(source_token, SUPPLY - AMOUNT),
+            Expect::Supply(mint, SUPPLY - AMOUNT),
+            Expect::Delegate(source_token, Some((delegate.pubkey(), delegated_amount))),
+        ],
+        AlternateOp::CloseAccount => vec![Expect::Closed(source_token)],
+    };
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!(
+            "Post-state mismatch for {:?} (alternate: {}): {}",
+            op, use_alternate, e
+        );
+    }
+    cu
+}
+
+pub fn benchmark_alternate_authorities() {
+    let (Some(token_ops_bytes), Some(token_ops_2022_bytes)) =
+        (load_program("token-ops"), load_program("token-ops-2022"))
+    else {
+        return;
+    };
+
+    println!(
+        "{:<36} {:>10} {:>10} {:>10} {:>10}",
+        "Operation", "Token alt", "Token own", "T22 alt", "T22 own"
+    );
+    println!("{}", "-".repeat(80));
+
+    for op in [
+        AlternateOp::Transfer,
+        AlternateOp::TransferChecked,
+        AlternateOp::Burn,
+        AlternateOp::BurnChecked,
+        AlternateOp::CloseAccount,
+    ] {
+        let token_cu = run_alternate_benchmark(&token_ops_bytes, Backend::Token, op, true);
+        let token_owner_cu = run_alternate_benchmark(&token_ops_bytes, Backend::Token, op, false);
+        let token_2022_cu =
+            run_alternate_benchmark(&token_ops_2022_bytes, Backend::Token2022, op, true);
+        let token_2022_owner_cu =
+            run_alternate_benchmark(&token_ops_2022_bytes, Backend::Token2022, op, false);
+
+        // "alt" columns are signed by the alternate authority, "own" by the owner
+        println!(
+            "{:<36} {:>10} {:>10} {:>10} {:>10}",
+            format!("{:?} ({})", op, op.alternate_authority()),
+            token_cu,
+            token_owner_cu,
+            token_2022_cu,
+            token_2022_owner_cu
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Compliance-style mint benchmarks: a permanent delegate transferring and
+//! burning from a holder that never signs, next to the holder doing the same,
+//! and a pausable mint paused and resumed around transfers that must fail
+//! while it is paused.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token_2022::error::TokenError;
+
+use crate::{
+    fixture_account,
+    fixtures::{AccountExtension, MintBuilder, MintExtension, TokenAccountBuilder},
+    load_program,
+    scenario::{print_results, run_steps, Step, StepResult},
+    send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+const DECIMALS: u8 = 9;
+
+/// Balance of the fixture holder token account
+const HOLDER_BALANCE: u64 = 1_000_000;
+
+/// Amount moved or burned by every operation
+const AMOUNT: u64 = 1000;
+
+#[derive(Clone, Copy, Debug)]
+enum DelegateOp {
+    TransferChecked,
+    Burn,
+    BurnChecked,
+}
+
+/// Runs `op` on a holder's tokens of a mint with a permanent delegate, signed
+/// by the delegate when `by_delegate` is set and by the holder otherwise
+fn run_permanent_delegate_benchmark(
+    token_ops_bytes: &[u8],
+    op: DelegateOp,
+    by_delegate: bool,
+) -> u64 {
+    let backend = Backend::Token2022;
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let delegate = Keypair::new();
+    let holder = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&delegate.pubkey())
+        .decimals(DECIMALS)
+        .supply(HOLDER_BALANCE)
+        .build_token_2022(&[MintExtension::PermanentDelegate(delegate.pubkey())]);
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    // The holder has no delegate of its own and never approved the mint's
+    let holder_token = Pubkey::new_unique();
+    let holder_data = TokenAccountBuilder::new(&mint, &holder.pubkey())
+        .amount(HOLDER_BALANCE)
+        .build_token_2022(&[]);
+    svm.set_account(
+        holder_token,
+        fixture_account(backend.token_program(), holder_data),
+    )
+    .unwrap();
+
+    // Receives the transferred tokens
+    let treasury_token = Pubkey::new_unique();
+    let treasury_data = TokenAccountBuilder::new(&mint, &delegate.pubkey()).build_token_2022(&[]);
+    svm.set_account(
+        treasury_token,
+        fixture_account(backend.token_program(), treasury_data),
+    )
+    .unwrap();
+
+    let authority = if by_delegate { &delegate } else { &holder };
+    let authority_meta = AccountMeta::new_readonly(authority.pubkey(), true);
+    let token_program = AccountMeta::new_readonly(backend.token_program(), false);
+    let burn_accounts = vec![
+        AccountMeta::new(holder_token, false),
+        AccountMeta::new(mint, false),
+        authority_meta.clone(),
+        token_program.clone(),
+    ];
+    let burned = vec![
+        Expect::Amount(holder_token, HOLDER_BALANCE - AMOUNT),
+        Expect::Supply(mint, HOLDER_BALANCE - AMOUNT),
+    ];
+
+    let (accounts, data, expected) = match op {
+        DelegateOp::TransferChecked => (
+            vec![
+                AccountMeta::new(holder_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(treasury_token, false),
+                authority_meta,
+                token_program,
+            ],
+            [&[8u8][..], &AMOUNT.to_le_bytes(), &[DECIMALS]].concat(),
+            vec![
+                Expect::Amount(holder_token, HOLDER_BALANCE - AMOUNT),
+                Expect::Amount(treasury_token, AMOUNT),
+            ],
+        ),
+        DelegateOp::Burn => (
+            burn_accounts,
+            [&[2u8][..], &AMOUNT.to_le_bytes()].concat(),
+            burned,
+        ),
+        DelegateOp::BurnChecked => (
+            burn_accounts,
+            [&[19u8][..], &AMOUNT.to_le_bytes(), &[DECIMALS]].concat(),
+            burned,
+        ),
+    };
+
+    let signer = if by_delegate {
+        "permanent delegate"
+    } else {
+        "holder"
+    };
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[authority]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {:?} ({}): {:?}", op, signer, e),
+    };
+
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!("Post-state mismatch for {:?} ({}): {}", op, signer, e);
+    }
+    cu
+}
+
+/// An unpaused pausable mint with `authority` as every authority and owning
+/// both token accounts, the source funded
+struct PausableScenario {
+    svm: LiteSVM,
+    payer: Keypair,
+    authority: Keypair,
+    mint: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+impl PausableScenario {
+    fn new(token_ops_bytes: &[u8]) -> Self {
+        let backend = Backend::Token2022;
+        let mut svm = LiteSVM::new();
+        svm.add_program(backend.token_program(), backend.program_bytes());
+        svm.add_program(backend.token_ops(), token_ops_bytes);
+
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        let mint_extensions = [MintExtension::Pausable {
+            authority: authority.pubkey(),
+            paused: false,
+        }];
+        let account_extensions = AccountExtension::required_by(&mint_extensions);
+
+        let mint = Pubkey::new_unique();
+        let mint_data = MintBuilder::new(&authority.pubkey())
+            .decimals(DECIMALS)
+            .supply(HOLDER_BALANCE)
+            .build_token_2022(&mint_extensions);
+        svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+            .unwrap();
+
+        let source = Pubkey::new_unique();
+        let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+            .amount(HOLDER_BALANCE)
+            .build_token_2022(&account_extensions);
+        svm.set_account(
+            source,
+            fixture_account(backend.token_program(), source_data),
+        )
+        .unwrap();
+
+        let destination = Pubkey::new_unique();
+        let destination_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+            .build_token_2022(&account_extensions);
+        svm.set_account(
+            destination,
+            fixture_account(backend.token_program(), destination_data),
+        )
+        .unwrap();
+
+        Self {
+            svm,
+            payer,
+            authority,
+            mint,
+            source,
+            destination,
+        }
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        let token_program = AccountMeta::new_readonly(Backend::Token2022.token_program(), false);
+        let authority = AccountMeta::new_readonly(self.authority.pubkey(), true);
+        let set_paused = vec![
+            AccountMeta::new(self.mint, false),
+            authority.clone(),
+            token_program.clone(),
+        ];
+        let transfer = vec![
+            AccountMeta::new(self.source, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.destination, false),
+            authority,
+            token_program,
+        ];
+        let transfer_data = [&[8u8][..], &AMOUNT.to_le_bytes(), &[DECIMALS]].concat();
+        // Balances after `transfers` successful transfers
+        let balances = |transfers: u64| {
+            vec![
+                Expect::Amount(self.source, HOLDER_BALANCE - transfers * AMOUNT),
+                Expect::Amount(self.destination, transfers * AMOUNT),
+            ]
+        };
+
+        vec![
+            Step {
+                label: "TransferChecked (unpaused)",
+                accounts: transfer.clone(),
+                data: transfer_data.clone(),
+                error: None,
+                expected: balances(1),
+            },
+            Step {
+                label: "Pause",
+                accounts: set_paused.clone(),
+                data: vec![51u8],
+                error: None,
+                expected: vec![Expect::Paused(self.mint, true)],
+            },
+            Step {
+                label: "TransferChecked (paused)",
+                accounts: transfer.clone(),
+                data: transfer_data.clone(),
+                error: Some(TokenError::MintPaused),
+                expected: balances(1),
+            },
+            Step {
+                label: "Resume",
+                accounts: set_paused,
+                data: vec![52u8],
+                error: None,
+                expected: vec![Expect::Paused(self.mint, false)],
+            },
+            Step {
+                label: "TransferChecked (resumed)",
+                accounts: transfer,
+                data: transfer_data,
+                error: None,
+                expected: balances(2),
+            },
+        ]
+    }
+
+    /// Runs every step in order, the same transfer before, during and after
+    /// the pause
+    fn run(&mut self) -> Vec<StepResult> {
+        let steps = self.steps();
+        run_steps(
+            &mut self.svm,
+            Backend::Token2022,
+            &self.payer,
+            &[&self.authority],
+            steps,
+        )
+    }
+}
+
+pub fn benchmark_permanent_delegate() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!(
+        "{:<45} {:>12} {:>12} {:>12}",
+        "Operation", "Holder", "Delegate", "Delta"
+    );
+    println!("{}", "-".repeat(84));
+
+    for op in [
+        DelegateOp::TransferChecked,
+        DelegateOp::Burn,
+        DelegateOp::BurnChecked,
+    ] {
+        let holder_cu = run_permanent_delegate_benchmark(&token_ops_2022_bytes, op, false);
+        let delegate_cu = run_permanent_delegate_benchmark(&token_ops_2022_bytes, op, true);
+        println!(
+            "{:<45} {:>12} {:>12} {:>+12}",
+            format!("{:?}", op),
+            holder_cu,
+            delegate_cu,
+            delegate_cu as i64 - holder_cu as i64
+        );
+    }
+}
+
+pub fn benchmark_pausable() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    let mut scenario = PausableScenario::new(&token_ops_2022_bytes);
+    print_results(&scenario.run());
+}
@@ -0,0 +1,206 @@
+//! Default account state scenario: token accounts of a mint that creates
+//! them Frozen, initialized, rejected as a transfer destination, thawed and
+//! then paid, and the default switched back to Initialized, one
+//! token-ops-2022 instruction per step.
+
+use litesvm::LiteSVM;
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_token::state::AccountState;
+use spl_token_2022::error::TokenError;
+
+use crate::{
+    fixture_account,
+    fixtures::{token_2022_account_len, MintBuilder, MintExtension, TokenAccountBuilder},
+    load_program,
+    scenario::{print_results, run_steps, Step, StepResult},
+    verify::Expect,
+    Backend,
+};
+
+const DECIMALS: u8 = 9;
+
+/// Balance of the fixture source token account
+const SOURCE_BALANCE: u64 = 1_000_000;
+
+/// Amount moved by every transfer
+const AMOUNT: u64 = 1000;
+
+/// A mint defaulting to Frozen with `authority` as every authority, a funded
+/// source token account and two uninitialized token accounts
+struct DefaultStateScenario {
+    svm: LiteSVM,
+    payer: Keypair,
+    authority: Keypair,
+    mint: Pubkey,
+    source: Pubkey,
+    frozen: Pubkey,
+    initialized: Pubkey,
+}
+
+impl DefaultStateScenario {
+    fn new(token_ops_bytes: &[u8]) -> Self {
+        let backend = Backend::Token2022;
+        let mut svm = LiteSVM::new();
+        svm.add_program(backend.token_program(), backend.program_bytes());
+        svm.add_program(backend.token_ops(), token_ops_bytes);
+
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        // Token-2022 requires a freeze authority to default to Frozen
+        let mint = Pubkey::new_unique();
+        let mint_data = MintBuilder::new(&authority.pubkey())
+            .decimals(DECIMALS)
+            .supply(SOURCE_BALANCE)
+            .freeze_authority(&authority.pubkey())
+            .build_token_2022(&[MintExtension::DefaultAccountState(AccountState::Frozen)]);
+        svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+            .unwrap();
+
+        // Created before the default applied, so not frozen
+        let source = Pubkey::new_unique();
+        let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+            .amount(SOURCE_BALANCE)
+            .build_token_2022(&[]);
+        svm.set_account(
+            source,
+            fixture_account(backend.token_program(), source_data),
+        )
+        .unwrap();
+
+        // Zeroed accounts already owned by the token program, as after a
+        // system program CreateAccount; DefaultAccountState adds no account
+        // extensions
+        let mut empty_account = || {
+            let pubkey = Pubkey::new_unique();
+            svm.set_account(
+                pubkey,
+                fixture_account(
+                    backend.token_program(),
+                    vec![0u8; token_2022_account_len(&[])],
+                ),
+            )
+            .unwrap();
+            pubkey
+        };
+        let frozen = empty_account();
+        let initialized = empty_account();
+
+        Self {
+            svm,
+            payer,
+            authority,
+            mint,
+            source,
+            frozen,
+            initialized,
+        }
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        let token_program = AccountMeta::new_readonly(Backend::Token2022.token_program(), false);
+        let authority = self.authority.pubkey();
+        let initialize_account = |account: Pubkey| {
+            vec![
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(authority, false),
+                token_program.clone(),
+            ]
+        };
+        let transfer = vec![
+            AccountMeta::new(self.source, false),
+            AccountMeta::new_readonly(self.mint, false),
+            AccountMeta::new(self.frozen, false),
+            AccountMeta::new_readonly(authority, true),
+            token_program.clone(),
+        ];
+        let transfer_data = [&[8u8][..], &AMOUNT.to_le_bytes(), &[DECIMALS]].concat();
+
+        vec![
+            Step {
+                label: "InitializeAccount3 (defaults to Frozen)",
+                accounts: initialize_account(self.frozen),
+                data: vec![13u8],
+                error: None,
+                expected: vec![Expect::State(self.frozen, AccountState::Frozen)],
+            },
+            Step {
+                label: "TransferChecked into the frozen account",
+                accounts: transfer.clone(),
+                data: transfer_data.clone(),
+                error: Some(TokenError::AccountFrozen),
+                expected: vec![
+                    Expect::Amount(self.source, SOURCE_BALANCE),
+                    Expect::Amount(self.frozen, 0),
+                ],
+            },
+            Step {
+                label: "ThawAccount",
+                accounts: vec![
+                    AccountMeta::new(self.frozen, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new_readonly(authority, true),
+                    token_program.clone(),
+                ],
+                data: vec![7u8],
+                error: None,
+                expected: vec![Expect::State(self.frozen, AccountState::Initialized)],
+            },
+            Step {
+                label: "TransferChecked into the thawed account",
+                accounts: transfer,
+                data: transfer_data,
+                error: None,
+                expected: vec![
+                    Expect::Amount(self.source, SOURCE_BALANCE - AMOUNT),
+                    Expect::Amount(self.frozen, AMOUNT),
+                ],
+            },
+            Step {
+                label: "UpdateDefaultAccountState (Initialized)",
+                accounts: vec![
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new_readonly(authority, true),
+                    token_program.clone(),
+                ],
+                data: vec![50u8, AccountState::Initialized as u8],
+                error: None,
+                expected: vec![Expect::DefaultAccountState(
+                    self.mint,
+                    AccountState::Initialized,
+                )],
+            },
+            Step {
+                label: "InitializeAccount3 (defaults to Initialized)",
+                accounts: initialize_account(self.initialized),
+                data: vec![13u8],
+                error: None,
+                expected: vec![Expect::State(self.initialized, AccountState::Initialized)],
+            },
+        ]
+    }
+
+    /// Runs every step in order, the transfer retried unchanged once the
+    /// account is thawed
+    fn run(&mut self) -> Vec<StepResult> {
+        let steps = self.steps();
+        run_steps(
+            &mut self.svm,
+            Backend::Token2022,
+            &self.payer,
+            &[&self.authority],
+            steps,
+        )
+    }
+}
+
+pub fn benchmark_default_state() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    let mut scenario = DefaultStateScenario::new(&token_ops_2022_bytes);
+    print_results(&scenario.run());
+}
@@ -0,0 +1,243 @@
+//! Duplicate-account benchmarks: the same instruction with an account
+//! passed twice versus with distinct accounts, for the write programs and
+//! token-ops.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, TokenAccountBuilder},
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TOKEN_OPS_PROGRAM_ID, WRITE_COPY_PROGRAM_ID, WRITE_LOOP_PROGRAM_ID,
+};
+
+/// Extra accounts appended to the write programs' single account
+const WRITE_EXTRA_COUNTS: &[usize] = &[1, 4, 16];
+
+/// Remaining accounts TransferWithRemaining forwards into the CPI
+const FORWARDED_COUNT: usize = 4;
+
+/// Where the duplicate appears in a token-ops Transfer
+#[derive(Clone, Copy)]
+enum TransferDuplicate {
+    /// Destination is the source account
+    SelfTransfer,
+    /// Authority is the fee payer
+    PayerAuthority,
+    /// TransferWithRemaining forwards copies of the source account
+    ForwardedSource,
+}
+
+impl TransferDuplicate {
+    fn label(self) -> String {
+        match self {
+            TransferDuplicate::SelfTransfer => "Transfer (source = destination)".to_string(),
+            TransferDuplicate::PayerAuthority => "Transfer (authority = payer)".to_string(),
+            TransferDuplicate::ForwardedSource => {
+                format!("TransferWithRemaining (+{} x source)", FORWARDED_COUNT)
+            }
+        }
+    }
+}
+
+pub fn benchmark_duplicate_accounts() {
+    let (Some(write_loop_bytes), Some(write_copy_bytes), Some(token_ops_bytes)) = (
+        load_program("write-loop"),
+        load_program("write-copy"),
+        load_program("token-ops"),
+    ) else {
+        return;
+    };
+
+    println!(
+        "{:<40} {:>10} {:>10} {:>10}",
+        "Case", "Distinct", "Duplicate", "Diff"
+    );
+    println!("{}", "-".repeat(73));
+
+    for (program_bytes, program_id, name) in [
+        (&write_loop_bytes, WRITE_LOOP_PROGRAM_ID, "write-loop"),
+        (&write_copy_bytes, WRITE_COPY_PROGRAM_ID, "write-copy"),
+    ] {
+        for &extra in WRITE_EXTRA_COUNTS {
+            let distinct = run_write(program_bytes, program_id, extra, false);
+            let duplicate = run_write(program_bytes, program_id, extra, true);
+            print_row(
+                &format!("{} (+{} accounts)", name, extra),
+                distinct,
+                duplicate,
+            );
+        }
+    }
+
+    for case in [
+        TransferDuplicate::SelfTransfer,
+        TransferDuplicate::PayerAuthority,
+        TransferDuplicate::ForwardedSource,
+    ] {
+        let distinct = run_transfer(&token_ops_bytes, case, false);
+        let duplicate = run_transfer(&token_ops_bytes, case, true);
+        print_row(&case.label(), distinct, duplicate);
+    }
+}
+
+fn print_row(label: &str, distinct: u64, duplicate: u64) {
+    println!(
+        "{:<40} {:>10} {:>10} {:>10}",
+        label,
+        distinct,
+        duplicate,
+        duplicate as i64 - distinct as i64
+    );
+}
+
+/// Runs a write program with `extra` writable accounts after its own, either
+/// all copies of that account or distinct empty accounts
+fn run_write(program_bytes: &[u8], program_id: Pubkey, extra: usize, duplicate: bool) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(program_id, program_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let account_pubkey = Pubkey::new_unique();
+    svm.set_account(account_pubkey, fixture_account(program_id, vec![0u8; 100]))
+        .unwrap();
+
+    let mut accounts = vec![AccountMeta::new(account_pubkey, false)];
+    for _ in 0..extra {
+        let pubkey = if duplicate {
+            account_pubkey
+        } else {
+            // Same owner and data as the duplicated account, so only the
+            // duplicate handling differs
+            let pubkey = Pubkey::new_unique();
+            svm.set_account(pubkey, fixture_account(program_id, vec![0u8; 100]))
+                .unwrap();
+            pubkey
+        };
+        accounts.push(AccountMeta::new(pubkey, false));
+    }
+
+    let instruction = Instruction {
+        program_id,
+        accounts,
+        data: vec![],
+    };
+    match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for {} extra accounts (duplicate: {}): {:?}",
+            extra, duplicate, e
+        ),
+    }
+}
+
+/// Runs a token-ops Transfer with the `case` duplicate, or with a distinct
+/// account in its place
+fn run_transfer(token_ops_bytes: &[u8], case: TransferDuplicate, duplicate: bool) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(spl_token::ID, include_bytes!("spl_token.so"));
+    svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+
+    let payer = Keypair::new();
+    let separate_authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let authority = match case {
+        TransferDuplicate::PayerAuthority if duplicate => &payer,
+        _ => &separate_authority,
+    };
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&authority.pubkey())
+        .supply(1_000_000_000)
+        .build();
+    svm.set_account(mint, fixture_account(spl_token::ID, mint_data))
+        .unwrap();
+
+    let new_token_account = |svm: &mut LiteSVM, amount: u64| {
+        let token_account = Pubkey::new_unique();
+        let data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+            .amount(amount)
+            .build();
+        svm.set_account(token_account, fixture_account(spl_token::ID, data))
+            .unwrap();
+        token_account
+    };
+    let source_token = new_token_account(&mut svm, 1_000_000_000);
+    let dest_token = match case {
+        TransferDuplicate::SelfTransfer if duplicate => source_token,
+        _ => new_token_account(&mut svm, 0),
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(source_token, false),
+        AccountMeta::new(dest_token, false),
+        AccountMeta::new_readonly(authority.pubkey(), true),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    let discriminator = match case {
+        TransferDuplicate::ForwardedSource => {
+            for _ in 0..FORWARDED_COUNT {
+                let forwarded = if duplicate {
+                    source_token
+                } else {
+                    new_token_account(&mut svm, 0)
+                };
+                accounts.push(AccountMeta::new(forwarded, false));
+            }
+            16u8 // discriminator for TransferWithRemaining
+        }
+        _ => 0u8, // discriminator for Transfer
+    };
+    let mut data = vec![discriminator];
+    data.extend_from_slice(&1000u64.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: TOKEN_OPS_PROGRAM_ID,
+        accounts,
+        data,
+    };
+    // The payer already signs; a separate authority signs in addition
+    let signers: &[&Keypair] = if std::ptr::eq(authority, &payer) {
+        &[]
+    } else {
+        &[authority]
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, signers) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for {} (duplicate: {}): {:?}",
+            case.label(),
+            duplicate,
+            e
+        ),
+    };
+
+    // A self-transfer leaves the balance where it was
+    let expected = if source_token == dest_token {
+        vec![Expect::Amount(source_token, 1_000_000_000)]
+    } else {
+        vec![
+            Expect::Amount(source_token, 1_000_000_000 - 1000),
+            Expect::Amount(dest_token, 1000),
+        ]
+    };
+    if let Err(e) = verify_post_state(&svm, Backend::Token, &expected) {
+        panic!(
+            "Post-state mismatch for {} (duplicate: {}): {}",
+            case.label(),
+            duplicate,
+            e
+        );
+    }
+    cu
+}
@@ -0,0 +1,320 @@
+//! Token-2022 extension fixtures: one case per extension, each a mint and
+//! token account carrying that extension plus whatever companion extensions
+//! Token-2022 itself would add, and the CU each adds to the base token ops.
+
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use crate::{
+    fixtures::{
+        token_2022_account_len, AccountExtension, MintBuilder, MintExtension, TokenAccountBuilder,
+    },
+    load_program, run_token_2022_benchmark, OpVariant, TokenOp, BASE_TOKEN_OPS,
+};
+
+/// A mint and token account extension set
+pub struct ExtensionCase {
+    pub name: &'static str,
+    pub mint_extensions: Vec<MintExtension>,
+    pub account_extensions: Vec<AccountExtension>,
+}
+
+impl ExtensionCase {
+    /// No extensions: the legacy 82 and 165-byte layouts
+    pub fn plain() -> Self {
+        Self {
+            name: "(none)",
+            mint_extensions: vec![],
+            account_extensions: vec![],
+        }
+    }
+
+    /// A mint extension, with the account extensions Token-2022 gives every
+    /// account of such a mint
+    fn mint(name: &'static str, mint_extensions: Vec<MintExtension>) -> Self {
+        let account_extensions = AccountExtension::required_by(&mint_extensions);
+        Self {
+            name,
+            mint_extensions,
+            account_extensions,
+        }
+    }
+
+    /// An account extension on an account of a plain mint
+    fn account(name: &'static str, extension: AccountExtension) -> Self {
+        Self {
+            name,
+            mint_extensions: vec![],
+            account_extensions: vec![extension],
+        }
+    }
+
+    /// Length InitializeAccount needs for an account of this case's mint,
+    /// which Token-2022 gives the mint's required account extensions
+    pub fn initialized_account_len(&self) -> usize {
+        token_2022_account_len(&AccountExtension::required_by(&self.mint_extensions))
+    }
+
+    /// Fee a TransferChecked of `amount` withholds on this case's mint
+    pub fn transfer_fee(&self, amount: u64) -> u64 {
+        self.mint_extensions
+            .iter()
+            .find_map(|extension| match *extension {
+                MintExtension::TransferFeeConfig {
+                    basis_points,
+                    maximum_fee,
+                    ..
+                } => {
+                    let fee = (amount as u128 * basis_points as u128).div_ceil(10_000);
+                    Some((fee as u64).min(maximum_fee))
+                }
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    /// Whether Token-2022 rejects `op`, run with its default arguments and
+    /// signed by the account owner through token-ops, on this case's
+    /// fixtures
+    pub fn rejects(&self, op: TokenOp) -> bool {
+        let has_account_extension =
+            |matches: fn(&AccountExtension) -> bool| self.account_extensions.iter().any(matches);
+        let non_transferable =
+            has_account_extension(|e| matches!(e, AccountExtension::NonTransferableAccount));
+        let memo_required = has_account_extension(|e| {
+            matches!(
+                e,
+                AccountExtension::MemoTransfer {
+                    require_incoming_transfer_memos: true
+                }
+            )
+        });
+        // CPI Guard blocks owner-signed transfers, burns and approvals in a CPI
+        let cpi_guarded =
+            has_account_extension(|e| matches!(e, AccountExtension::CpiGuard { lock_cpi: true }));
+        // These account extensions need the mint, which Transfer lacks
+        let mint_required = has_account_extension(|e| {
+            matches!(
+                e,
+                AccountExtension::TransferFeeAmount { .. }
+                    | AccountExtension::TransferHookAccount
+                    | AccountExtension::PausableAccount
+            )
+        });
+        // The harness passes no hook program or extra accounts
+        let transfer_hook = self
+            .mint_extensions
+            .iter()
+            .any(|e| matches!(e, MintExtension::TransferHook { .. }));
+
+        match op {
+            TokenOp::Transfer => non_transferable || memo_required || cpi_guarded || mint_required,
+            TokenOp::TransferChecked => {
+                non_transferable || memo_required || cpi_guarded || transfer_hook
+            }
+            TokenOp::Burn | TokenOp::BurnChecked | TokenOp::Approve | TokenOp::ApproveChecked => {
+                cpi_guarded
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Every extension case, with `authority` as each extension authority
+pub fn extension_cases(authority: &Pubkey) -> Vec<ExtensionCase> {
+    let authority = *authority;
+    let transfer_fee_config = MintExtension::TransferFeeConfig {
+        authority,
+        basis_points: 100,
+        maximum_fee: u64::MAX,
+        withheld_amount: 0,
+    };
+    vec![
+        ExtensionCase::mint("TransferFeeConfig", vec![transfer_fee_config]),
+        ExtensionCase::mint(
+            "MintCloseAuthority",
+            vec![MintExtension::MintCloseAuthority(authority)],
+        ),
+        ExtensionCase::mint(
+            "ConfidentialTransferMint",
+            vec![MintExtension::ConfidentialTransferMint { authority }],
+        ),
+        ExtensionCase::mint(
+            "ConfidentialTransferFeeConfig",
+            vec![
+                transfer_fee_config,
+                MintExtension::ConfidentialTransferMint { authority },
+                MintExtension::ConfidentialTransferFeeConfig { authority },
+            ],
+        ),
+        ExtensionCase::mint(
+            "DefaultAccountState",
+            vec![MintExtension::DefaultAccountState(
+                AccountState::Initialized,
+            )],
+        ),
+        ExtensionCase::mint("NonTransferable", vec![MintExtension::NonTransferable]),
+        ExtensionCase::mint(
+            "InterestBearingConfig",
+            vec![MintExtension::InterestBearingConfig {
+                authority,
+                rate: 500,
+            }],
+        ),
+        ExtensionCase::mint(
+            "PermanentDelegate",
+            vec![MintExtension::PermanentDelegate(authority)],
+        ),
+        ExtensionCase::mint(
+            "TransferHook",
+            vec![MintExtension::TransferHook {
+                authority,
+                program_id: Pubkey::new_unique(),
+            }],
+        ),
+        ExtensionCase::mint(
+            "MetadataPointer",
+            vec![MintExtension::MetadataPointer {
+                authority,
+                metadata_address: Pubkey::new_unique(),
+            }],
+        ),
+        ExtensionCase::mint(
+            "GroupPointer",
+            vec![MintExtension::GroupPointer {
+                authority,
+                group_address: Pubkey::new_unique(),
+            }],
+        ),
+        ExtensionCase::mint(
+            "GroupMemberPointer",
+            vec![MintExtension::GroupMemberPointer {
+                authority,
+                member_address: Pubkey::new_unique(),
+            }],
+        ),
+        ExtensionCase::mint(
+            "ScaledUiAmount",
+            vec![MintExtension::ScaledUiAmount {
+                authority,
+                multiplier: 2.0,
+            }],
+        ),
+        ExtensionCase::mint(
+            "Pausable",
+            vec![MintExtension::Pausable {
+                authority,
+                paused: false,
+            }],
+        ),
+        ExtensionCase::account("ImmutableOwner", AccountExtension::ImmutableOwner),
+        ExtensionCase::account(
+            "MemoTransfer",
+            AccountExtension::MemoTransfer {
+                require_incoming_transfer_memos: true,
+            },
+        ),
+        ExtensionCase::account("CpiGuard", AccountExtension::CpiGuard { lock_cpi: true }),
+    ]
+}
+
+/// The extension case whose authority Token-2022 SetAuthority
+/// `authority_type` changes, or the plain case for the base authority types
+pub fn authority_type_case(authority_type: u8, authority: &Pubkey) -> ExtensionCase {
+    let name = match authority_type {
+        4 | 5 => "TransferFeeConfig",
+        6 => "MintCloseAuthority",
+        7 => "InterestBearingConfig",
+        8 => "PermanentDelegate",
+        9 => "ConfidentialTransferMint",
+        10 => "TransferHook",
+        11 => "ConfidentialTransferFeeConfig",
+        12 => "MetadataPointer",
+        13 => "GroupPointer",
+        14 => "GroupMemberPointer",
+        15 => "ScaledUiAmount",
+        16 => "Pausable",
+        _ => return ExtensionCase::plain(),
+    };
+    extension_cases(authority)
+        .into_iter()
+        .find(|case| case.name == name)
+        .expect("every extension authority type has a case")
+}
+
+/// Reports the mint and token account data length of every extension case
+pub fn report_extension_fixtures() {
+    println!(
+        "{:<32} {:>12} {:>12}",
+        "Extension", "Mint bytes", "Account bytes"
+    );
+    println!("{}", "-".repeat(58));
+
+    let authority = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    println!(
+        "{:<32} {:>12} {:>12}",
+        "(none)",
+        Mint::LEN,
+        TokenAccount::LEN
+    );
+    for case in extension_cases(&authority) {
+        let mint_data = MintBuilder::new(&authority).build_token_2022(&case.mint_extensions);
+        let account_data =
+            TokenAccountBuilder::new(&mint, &authority).build_token_2022(&case.account_extensions);
+        println!(
+            "{:<32} {:>12} {:>12}",
+            case.name,
+            mint_data.len(),
+            account_data.len()
+        );
+    }
+}
+
+/// Runs every base op against every extension case and reports the CU each
+/// extension adds over the plain fixture
+pub fn benchmark_extension_surcharges() {
+    let Some(token_ops_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!(
+        "{:<45} {:>12} {:>12}",
+        "Operation / Extension", "CU Consumed", "Surcharge"
+    );
+    println!("{}", "-".repeat(71));
+
+    for &(op, label) in BASE_TOKEN_OPS {
+        // These initialize a fresh mint, which carries none of the fixture's extensions
+        if matches!(op, TokenOp::InitializeMint | TokenOp::InitializeMint2) {
+            continue;
+        }
+
+        let authority = Keypair::new();
+        let plain_cu = run_token_2022_benchmark(
+            &token_ops_bytes,
+            op,
+            OpVariant::Default,
+            &authority,
+            &ExtensionCase::plain(),
+        );
+        println!("{:<45} {:>12} {:>12}", label, plain_cu, "-");
+
+        for case in extension_cases(&authority.pubkey()) {
+            let label = format!("  {}", case.name);
+            let cu = run_token_2022_benchmark(
+                &token_ops_bytes,
+                op,
+                OpVariant::Default,
+                &authority,
+                &case,
+            );
+            if case.rejects(op) {
+                println!("{:<45} {:>12} {:>12}", label, "rejected", "-");
+            } else {
+                let surcharge = cu as i64 - plain_cu as i64;
+                println!("{:<45} {:>12} {:>+12}", label, cu, surcharge);
+            }
+        }
+    }
+}
@@ -0,0 +1,547 @@
+//! Builders for the mint, token account and multisig data harness cases
+//! start from. Each builder holds the `spl_token::state` type, packs it into
+//! account data and unpacks the result again, so a fixture can only hold a
+//! layout the token programs accept.
+//!
+//! Mints and token accounts also build as Token-2022 data carrying a set of
+//! extensions, laid out by Token-2022's own TLV code.
+
+use std::fmt::Debug;
+
+use solana_sdk::{
+    program_error::ProgramError, program_option::COption, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_token::{
+    instruction::MAX_SIGNERS,
+    state::{Account, AccountState, Mint, Multisig},
+};
+use spl_token_2022::extension::{
+    confidential_transfer::ConfidentialTransferMint,
+    confidential_transfer_fee::ConfidentialTransferFeeConfig,
+    cpi_guard::CpiGuard,
+    default_account_state::DefaultAccountState,
+    group_member_pointer::GroupMemberPointer,
+    group_pointer::GroupPointer,
+    immutable_owner::ImmutableOwner,
+    interest_bearing_mint::InterestBearingConfig,
+    memo_transfer::MemoTransfer,
+    metadata_pointer::MetadataPointer,
+    mint_close_authority::MintCloseAuthority,
+    non_transferable::{NonTransferable, NonTransferableAccount},
+    pausable::{PausableAccount, PausableConfig},
+    permanent_delegate::PermanentDelegate,
+    scaled_ui_amount::ScaledUiAmountConfig,
+    transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
+    transfer_hook::{TransferHook, TransferHookAccount},
+    BaseState, BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
+    StateWithExtensions, StateWithExtensionsMut,
+};
+
+/// Packs `state` into account data and checks it unpacks to the same value
+fn pack<T: Pack + Copy + Debug + PartialEq>(state: T) -> Vec<u8> {
+    let mut data = vec![0u8; T::LEN];
+    T::pack(state, &mut data).expect("fixture state does not pack");
+    let unpacked = T::unpack_unchecked(&data).expect("fixture data does not unpack");
+    assert_eq!(unpacked, state, "fixture data does not round-trip");
+    data
+}
+
+/// Mint data builder
+#[derive(Clone, Copy)]
+pub struct MintBuilder(Mint);
+
+impl MintBuilder {
+    /// Initialized mint with `mint_authority`, 9 decimals, no supply and no
+    /// freeze authority
+    pub fn new(mint_authority: &Pubkey) -> Self {
+        Self(Mint {
+            mint_authority: COption::Some(*mint_authority),
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        })
+    }
+
+    pub fn supply(mut self, supply: u64) -> Self {
+        self.0.supply = supply;
+        self
+    }
+
+    pub fn decimals(mut self, decimals: u8) -> Self {
+        self.0.decimals = decimals;
+        self
+    }
+
+    pub fn freeze_authority(mut self, freeze_authority: &Pubkey) -> Self {
+        self.0.freeze_authority = COption::Some(*freeze_authority);
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        pack(self.0)
+    }
+
+    /// Builds Token-2022 mint data carrying `extensions`. Without extensions
+    /// this is the same 82 bytes `build` returns.
+    pub fn build_token_2022(self, extensions: &[MintExtension]) -> Vec<u8> {
+        let mint = spl_token_2022::state::Mint {
+            mint_authority: self.0.mint_authority,
+            supply: self.0.supply,
+            decimals: self.0.decimals,
+            is_initialized: self.0.is_initialized,
+            freeze_authority: self.0.freeze_authority,
+        };
+        let types: Vec<ExtensionType> = extensions.iter().map(|e| e.extension_type()).collect();
+        pack_token_2022(mint, &types, |state| {
+            extensions
+                .iter()
+                .try_for_each(|extension| extension.init(state))
+        })
+    }
+}
+
+/// Token account data builder
+#[derive(Clone, Copy)]
+pub struct TokenAccountBuilder(Account);
+
+impl TokenAccountBuilder {
+    /// Initialized, empty token account of `mint` owned by `owner`
+    pub fn new(mint: &Pubkey, owner: &Pubkey) -> Self {
+        Self(Account {
+            mint: *mint,
+            owner: *owner,
+            amount: 0,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        })
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.0.amount = amount;
+        self
+    }
+
+    pub fn delegate(mut self, delegate: &Pubkey, delegated_amount: u64) -> Self {
+        self.0.delegate = COption::Some(*delegate);
+        self.0.delegated_amount = delegated_amount;
+        self
+    }
+
+    pub fn state(mut self, state: AccountState) -> Self {
+        self.0.state = state;
+        self
+    }
+
+    /// Makes the account wrapped SOL: `is_native` holds the rent-exempt
+    /// reserve, so the account's lamports must be `rent_reserve + amount`
+    pub fn native(mut self, rent_reserve: u64) -> Self {
+        self.0.is_native = COption::Some(rent_reserve);
+        self
+    }
+
+    pub fn close_authority(mut self, close_authority: &Pubkey) -> Self {
+        self.0.close_authority = COption::Some(*close_authority);
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        pack(self.0)
+    }
+
+    /// Builds Token-2022 token account data carrying `extensions`. Without
+    /// extensions this is the same 165 bytes `build` returns.
+    pub fn build_token_2022(self, extensions: &[AccountExtension]) -> Vec<u8> {
+        let account = spl_token_2022::state::Account {
+            mint: self.0.mint,
+            owner: self.0.owner,
+            amount: self.0.amount,
+            delegate: self.0.delegate,
+            // Both programs number the states the same way
+            state: (self.0.state as u8).try_into().unwrap(),
+            is_native: self.0.is_native,
+            delegated_amount: self.0.delegated_amount,
+            close_authority: self.0.close_authority,
+        };
+        let types: Vec<ExtensionType> = extensions.iter().map(|e| e.extension_type()).collect();
+        pack_token_2022(account, &types, |state| {
+            extensions
+                .iter()
+                .try_for_each(|extension| extension.init(state))
+        })
+    }
+}
+
+/// Multisig data builder
+#[derive(Clone, Copy)]
+pub struct MultisigBuilder(Multisig);
+
+impl MultisigBuilder {
+    /// Initialized `m`-of-`signers.len()` multisig
+    pub fn new(m: u8, signers: &[Pubkey]) -> Self {
+        assert!(
+            signers.len() <= MAX_SIGNERS,
+            "a multisig holds at most {} signers",
+            MAX_SIGNERS
+        );
+        let mut multisig = Multisig {
+            m,
+            n: signers.len() as u8,
+            is_initialized: true,
+            ..Multisig::default()
+        };
+        multisig.signers[..signers.len()].copy_from_slice(signers);
+        Self(multisig)
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        pack(self.0)
+    }
+}
+
+/// Length of Token-2022 mint data carrying `extensions`
+pub fn token_2022_mint_len(extensions: &[MintExtension]) -> usize {
+    let types: Vec<ExtensionType> = extensions.iter().map(|e| e.extension_type()).collect();
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&types)
+        .expect("invalid Token-2022 extension set")
+}
+
+/// Length of Token-2022 token account data carrying `extensions`
+pub fn token_2022_account_len(extensions: &[AccountExtension]) -> usize {
+    let types: Vec<ExtensionType> = extensions.iter().map(|e| e.extension_type()).collect();
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&types)
+        .expect("invalid Token-2022 extension set")
+}
+
+/// Packs a Token-2022 base state followed by the `types` extensions that
+/// `init` initializes, then checks the data unpacks to the same base state
+/// and extension list
+fn pack_token_2022<S: BaseState + Pack + Copy + Debug + PartialEq>(
+    base: S,
+    types: &[ExtensionType],
+    init: impl FnOnce(&mut StateWithExtensionsMut<S>) -> Result<(), ProgramError>,
+) -> Vec<u8> {
+    let len = ExtensionType::try_calculate_account_len::<S>(types)
+        .expect("invalid Token-2022 extension set");
+    let mut data = vec![0u8; len];
+    let mut state = StateWithExtensionsMut::<S>::unpack_uninitialized(&mut data)
+        .expect("fixture data does not unpack");
+    init(&mut state).expect("fixture extension does not initialize");
+    state.base = base;
+    state.pack_base();
+    state
+        .init_account_type()
+        .expect("fixture account type does not initialize");
+
+    let unpacked = StateWithExtensions::<S>::unpack(&data).expect("fixture data does not unpack");
+    assert_eq!(unpacked.base, base, "fixture data does not round-trip");
+    assert_eq!(
+        unpacked.get_extension_types().unwrap(),
+        types,
+        "fixture extensions do not round-trip"
+    );
+    data
+}
+
+fn optional_key(address: Pubkey) -> OptionalNonZeroPubkey {
+    OptionalNonZeroPubkey::try_from(Some(address)).expect("extension address is the default key")
+}
+
+/// Token-2022 mint extension and the configuration it is initialized with.
+/// Every authority an extension has is set to the one given.
+#[derive(Clone, Copy, Debug)]
+pub enum MintExtension {
+    /// The same fee for both epochs, and `withheld_amount` already
+    /// harvested into the mint
+    TransferFeeConfig {
+        authority: Pubkey,
+        basis_points: u16,
+        maximum_fee: u64,
+        withheld_amount: u64,
+    },
+    MintCloseAuthority(Pubkey),
+    /// Confidential transfers with no auditor, only the authority set
+    ConfidentialTransferMint {
+        authority: Pubkey,
+    },
+    DefaultAccountState(AccountState),
+    NonTransferable,
+    InterestBearingConfig {
+        authority: Pubkey,
+        rate: i16,
+    },
+    PermanentDelegate(Pubkey),
+    TransferHook {
+        authority: Pubkey,
+        program_id: Pubkey,
+    },
+    MetadataPointer {
+        authority: Pubkey,
+        metadata_address: Pubkey,
+    },
+    GroupPointer {
+        authority: Pubkey,
+        group_address: Pubkey,
+    },
+    GroupMemberPointer {
+        authority: Pubkey,
+        member_address: Pubkey,
+    },
+    /// Confidential transfer fees with only the authority set
+    ConfidentialTransferFeeConfig {
+        authority: Pubkey,
+    },
+    ScaledUiAmount {
+        authority: Pubkey,
+        multiplier: f64,
+    },
+    Pausable {
+        authority: Pubkey,
+        paused: bool,
+    },
+}
+
+impl MintExtension {
+    pub fn extension_type(self) -> ExtensionType {
+        match self {
+            MintExtension::TransferFeeConfig { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::MintCloseAuthority(_) => ExtensionType::MintCloseAuthority,
+            MintExtension::ConfidentialTransferMint { .. } => {
+                ExtensionType::ConfidentialTransferMint
+            }
+            MintExtension::DefaultAccountState(_) => ExtensionType::DefaultAccountState,
+            MintExtension::NonTransferable => ExtensionType::NonTransferable,
+            MintExtension::InterestBearingConfig { .. } => ExtensionType::InterestBearingConfig,
+            MintExtension::PermanentDelegate(_) => ExtensionType::PermanentDelegate,
+            MintExtension::TransferHook { .. } => ExtensionType::TransferHook,
+            MintExtension::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            MintExtension::GroupPointer { .. } => ExtensionType::GroupPointer,
+            MintExtension::GroupMemberPointer { .. } => ExtensionType::GroupMemberPointer,
+            MintExtension::ConfidentialTransferFeeConfig { .. } => {
+                ExtensionType::ConfidentialTransferFeeConfig
+            }
+            MintExtension::ScaledUiAmount { .. } => ExtensionType::ScaledUiAmount,
+            MintExtension::Pausable { .. } => ExtensionType::Pausable,
+        }
+    }
+
+    fn init(
+        self,
+        mint: &mut StateWithExtensionsMut<spl_token_2022::state::Mint>,
+    ) -> Result<(), ProgramError> {
+        match self {
+            MintExtension::TransferFeeConfig {
+                authority,
+                basis_points,
+                maximum_fee,
+                withheld_amount,
+            } => {
+                let config = mint.init_extension::<TransferFeeConfig>(false)?;
+                config.transfer_fee_config_authority = optional_key(authority);
+                config.withdraw_withheld_authority = optional_key(authority);
+                let fee = TransferFee {
+                    epoch: 0.into(),
+                    maximum_fee: maximum_fee.into(),
+                    transfer_fee_basis_points: basis_points.into(),
+                };
+                config.older_transfer_fee = fee;
+                config.newer_transfer_fee = fee;
+                config.withheld_amount = withheld_amount.into();
+            }
+            MintExtension::MintCloseAuthority(authority) => {
+                mint.init_extension::<MintCloseAuthority>(false)?
+                    .close_authority = optional_key(authority);
+            }
+            MintExtension::ConfidentialTransferMint { authority } => {
+                mint.init_extension::<ConfidentialTransferMint>(false)?
+                    .authority = optional_key(authority);
+            }
+            MintExtension::DefaultAccountState(state) => {
+                mint.init_extension::<DefaultAccountState>(false)?.state = state as u8;
+            }
+            MintExtension::NonTransferable => {
+                mint.init_extension::<NonTransferable>(false)?;
+            }
+            MintExtension::InterestBearingConfig { authority, rate } => {
+                let config = mint.init_extension::<InterestBearingConfig>(false)?;
+                config.rate_authority = optional_key(authority);
+                config.pre_update_average_rate = rate.into();
+                config.current_rate = rate.into();
+            }
+            MintExtension::PermanentDelegate(delegate) => {
+                mint.init_extension::<PermanentDelegate>(false)?.delegate = optional_key(delegate);
+            }
+            MintExtension::TransferHook {
+                authority,
+                program_id,
+            } => {
+                let hook = mint.init_extension::<TransferHook>(false)?;
+                hook.authority = optional_key(authority);
+                hook.program_id = optional_key(program_id);
+            }
+            MintExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => {
+                let pointer = mint.init_extension::<MetadataPointer>(false)?;
+                pointer.authority = optional_key(authority);
+                pointer.metadata_address = optional_key(metadata_address);
+            }
+            MintExtension::GroupPointer {
+                authority,
+                group_address,
+            } => {
+                let pointer = mint.init_extension::<GroupPointer>(false)?;
+                pointer.authority = optional_key(authority);
+                pointer.group_address = optional_key(group_address);
+            }
+            MintExtension::GroupMemberPointer {
+                authority,
+                member_address,
+            } => {
+                let pointer = mint.init_extension::<GroupMemberPointer>(false)?;
+                pointer.authority = optional_key(authority);
+                pointer.member_address = optional_key(member_address);
+            }
+            MintExtension::ConfidentialTransferFeeConfig { authority } => {
+                mint.init_extension::<ConfidentialTransferFeeConfig>(false)?
+                    .authority = optional_key(authority);
+            }
+            MintExtension::ScaledUiAmount {
+                authority,
+                multiplier,
+            } => {
+                let config = mint.init_extension::<ScaledUiAmountConfig>(false)?;
+                config.authority = optional_key(authority);
+                config.multiplier = multiplier.into();
+                config.new_multiplier = multiplier.into();
+            }
+            MintExtension::Pausable { authority, paused } => {
+                let config = mint.init_extension::<PausableConfig>(false)?;
+                config.authority = optional_key(authority);
+                config.paused = paused.into();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Token-2022 token account extension and the configuration it is
+/// initialized with
+#[derive(Clone, Copy, Debug)]
+pub enum AccountExtension {
+    TransferFeeAmount {
+        withheld_amount: u64,
+    },
+    ImmutableOwner,
+    MemoTransfer {
+        require_incoming_transfer_memos: bool,
+    },
+    NonTransferableAccount,
+    CpiGuard {
+        lock_cpi: bool,
+    },
+    TransferHookAccount,
+    PausableAccount,
+}
+
+impl AccountExtension {
+    pub fn extension_type(self) -> ExtensionType {
+        match self {
+            AccountExtension::TransferFeeAmount { .. } => ExtensionType::TransferFeeAmount,
+            AccountExtension::ImmutableOwner => ExtensionType::ImmutableOwner,
+            AccountExtension::MemoTransfer { .. } => ExtensionType::MemoTransfer,
+            AccountExtension::NonTransferableAccount => ExtensionType::NonTransferableAccount,
+            AccountExtension::CpiGuard { .. } => ExtensionType::CpiGuard,
+            AccountExtension::TransferHookAccount => ExtensionType::TransferHookAccount,
+            AccountExtension::PausableAccount => ExtensionType::PausableAccount,
+        }
+    }
+
+    /// Extensions Token-2022 gives every account of a mint carrying
+    /// `mint_extensions` when it initializes one, with empty state
+    pub fn required_by(mint_extensions: &[MintExtension]) -> Vec<AccountExtension> {
+        let mut extensions = Vec::new();
+        for extension in mint_extensions {
+            match extension {
+                MintExtension::TransferFeeConfig { .. } => {
+                    extensions.push(AccountExtension::TransferFeeAmount { withheld_amount: 0 })
+                }
+                MintExtension::NonTransferable => extensions.extend([
+                    AccountExtension::NonTransferableAccount,
+                    AccountExtension::ImmutableOwner,
+                ]),
+                MintExtension::TransferHook { .. } => {
+                    extensions.push(AccountExtension::TransferHookAccount)
+                }
+                MintExtension::Pausable { .. } => {
+                    extensions.push(AccountExtension::PausableAccount)
+                }
+                _ => {}
+            }
+        }
+        extensions
+    }
+
+    fn init(
+        self,
+        account: &mut StateWithExtensionsMut<spl_token_2022::state::Account>,
+    ) -> Result<(), ProgramError> {
+        match self {
+            AccountExtension::TransferFeeAmount { withheld_amount } => {
+                account
+                    .init_extension::<TransferFeeAmount>(false)?
+                    .withheld_amount = withheld_amount.into();
+            }
+            AccountExtension::ImmutableOwner => {
+                account.init_extension::<ImmutableOwner>(false)?;
+            }
+            AccountExtension::MemoTransfer {
+                require_incoming_transfer_memos,
+            } => {
+                account
+                    .init_extension::<MemoTransfer>(false)?
+                    .require_incoming_transfer_memos = require_incoming_transfer_memos.into();
+            }
+            AccountExtension::NonTransferableAccount => {
+                account.init_extension::<NonTransferableAccount>(false)?;
+            }
+            AccountExtension::CpiGuard { lock_cpi } => {
+                account.init_extension::<CpiGuard>(false)?.lock_cpi = lock_cpi.into();
+            }
+            AccountExtension::TransferHookAccount => {
+                account.init_extension::<TransferHookAccount>(false)?;
+            }
+            AccountExtension::PausableAccount => {
+                account.init_extension::<PausableAccount>(false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Token-2022 AccountType byte for mints
+pub const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Token-2022 AccountType byte for token accounts
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Pads Token-2022 mint or token account data to `len` bytes: the base state is
+/// zero-padded to the token account size, followed by the AccountType byte and
+/// zeroed TLV space that Token-2022 reads as holding no extensions
+pub fn pad_token_2022_data(mut data: Vec<u8>, account_type: u8, len: usize) -> Vec<u8> {
+    // The Multisig size is reserved: Token-2022 refuses to unpack it as anything else
+    assert!(
+        len > Account::LEN && len != Multisig::LEN,
+        "invalid Token-2022 account length {}",
+        len
+    );
+    data.resize(Account::LEN, 0);
+    data.push(account_type);
+    data.resize(len, 0);
+    data
+}
@@ -0,0 +1,283 @@
+//! Token account lifecycle scenario: a mint and two token accounts taken from
+//! empty accounts through minting, delegation, transfer, burn and close, one
+//! token-ops instruction per step, against both token programs.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey, signature::Keypair,
+    signer::Signer,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use crate::{
+    fixture_account, load_program,
+    scenario::{run_steps, Step, StepResult},
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+const DECIMALS: u8 = 9;
+
+/// Minted into the source account
+const MINTED: u64 = 1_000_000;
+
+/// Delegate allowance on the source account
+const APPROVED: u64 = 500_000;
+
+/// Moved by the delegate to the destination, which then burns all of it
+const TRANSFERRED: u64 = 200_000;
+
+/// The scenario's accounts, starting out empty
+struct Lifecycle {
+    svm: LiteSVM,
+    backend: Backend,
+    payer: Keypair,
+    owner: Keypair,
+    delegate: Keypair,
+    mint: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+impl Lifecycle {
+    fn new(token_ops_bytes: &[u8], backend: Backend) -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program(backend.token_program(), backend.program_bytes());
+        svm.add_program(backend.token_ops(), token_ops_bytes);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        // Zeroed accounts already owned by the token program, as after a
+        // system program CreateAccount
+        let mut empty_account = |len: usize| {
+            let pubkey = Pubkey::new_unique();
+            svm.set_account(
+                pubkey,
+                fixture_account(backend.token_program(), vec![0u8; len]),
+            )
+            .unwrap();
+            pubkey
+        };
+        let mint = empty_account(Mint::LEN);
+        let source = empty_account(TokenAccount::LEN);
+        let destination = empty_account(TokenAccount::LEN);
+
+        Self {
+            svm,
+            backend,
+            payer,
+            owner: Keypair::new(),
+            delegate: Keypair::new(),
+            mint,
+            source,
+            destination,
+        }
+    }
+
+    fn steps(&self) -> Vec<Step> {
+        let token_program = AccountMeta::new_readonly(self.backend.token_program(), false);
+        let owner = self.owner.pubkey();
+        let delegate = self.delegate.pubkey();
+        let with_amount = |discriminator: u8, amount: u64| {
+            let mut data = vec![discriminator];
+            data.extend_from_slice(&amount.to_le_bytes());
+            data
+        };
+
+        vec![
+            Step {
+                label: "InitializeMint2",
+                accounts: vec![
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new_readonly(owner, false),
+                    token_program.clone(),
+                ],
+                data: vec![10u8, DECIMALS, 0], // no freeze authority
+                error: None,
+                expected: vec![
+                    Expect::Supply(self.mint, 0),
+                    Expect::MintAuthority(self.mint, Some(owner)),
+                    Expect::FreezeAuthority(self.mint, None),
+                ],
+            },
+            Step {
+                label: "InitializeAccount3 (source)",
+                accounts: vec![
+                    AccountMeta::new(self.source, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new_readonly(owner, false),
+                    token_program.clone(),
+                ],
+                data: vec![13u8],
+                error: None,
+                expected: vec![
+                    Expect::Owner(self.source, owner),
+                    Expect::Amount(self.source, 0),
+                ],
+            },
+            Step {
+                label: "InitializeAccount3 (destination)",
+                accounts: vec![
+                    AccountMeta::new(self.destination, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new_readonly(owner, false),
+                    token_program.clone(),
+                ],
+                data: vec![13u8],
+                error: None,
+                expected: vec![
+                    Expect::Owner(self.destination, owner),
+                    Expect::Amount(self.destination, 0),
+                ],
+            },
+            Step {
+                label: "MintTo",
+                accounts: vec![
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new(self.source, false),
+                    AccountMeta::new_readonly(owner, true),
+                    token_program.clone(),
+                ],
+                data: with_amount(1, MINTED),
+                error: None,
+                expected: vec![
+                    Expect::Supply(self.mint, MINTED),
+                    Expect::Amount(self.source, MINTED),
+                ],
+            },
+            Step {
+                label: "Approve",
+                accounts: vec![
+                    AccountMeta::new(self.source, false),
+                    AccountMeta::new_readonly(delegate, false),
+                    AccountMeta::new_readonly(owner, true),
+                    token_program.clone(),
+                ],
+                data: with_amount(3, APPROVED),
+                error: None,
+                expected: vec![Expect::Delegate(self.source, Some((delegate, APPROVED)))],
+            },
+            Step {
+                label: "TransferChecked (delegate)",
+                accounts: vec![
+                    AccountMeta::new(self.source, false),
+                    AccountMeta::new_readonly(self.mint, false),
+                    AccountMeta::new(self.destination, false),
+                    AccountMeta::new_readonly(delegate, true),
+                    token_program.clone(),
+                ],
+                data: [with_amount(8, TRANSFERRED), vec![DECIMALS]].concat(),
+                error: None,
+                expected: vec![
+                    Expect::Amount(self.source, MINTED - TRANSFERRED),
+                    Expect::Amount(self.destination, TRANSFERRED),
+                    Expect::Delegate(self.source, Some((delegate, APPROVED - TRANSFERRED))),
+                ],
+            },
+            Step {
+                label: "Revoke",
+                accounts: vec![
+                    AccountMeta::new(self.source, false),
+                    AccountMeta::new_readonly(owner, true),
+                    token_program.clone(),
+                ],
+                data: vec![4u8],
+                error: None,
+                expected: vec![Expect::Delegate(self.source, None)],
+            },
+            Step {
+                label: "Burn (destination)",
+                accounts: vec![
+                    AccountMeta::new(self.destination, false),
+                    AccountMeta::new(self.mint, false),
+                    AccountMeta::new_readonly(owner, true),
+                    token_program.clone(),
+                ],
+                data: with_amount(2, TRANSFERRED),
+                error: None,
+                expected: vec![
+                    Expect::Supply(self.mint, MINTED - TRANSFERRED),
+                    Expect::Amount(self.destination, 0),
+                ],
+            },
+            Step {
+                label: "CloseAccount (destination)",
+                accounts: vec![
+                    AccountMeta::new(self.destination, false),
+                    AccountMeta::new(owner, false),
+                    AccountMeta::new_readonly(owner, true),
+                    token_program,
+                ],
+                data: vec![5u8],
+                error: None,
+                expected: vec![Expect::Closed(self.destination)],
+            },
+        ]
+    }
+
+    /// Runs every step in order, each signed by the owner or the delegate
+    fn run(&mut self) -> Vec<StepResult> {
+        let steps = self.steps();
+        run_steps(
+            &mut self.svm,
+            self.backend,
+            &self.payer,
+            &[&self.owner, &self.delegate],
+            steps,
+        )
+    }
+
+    /// Checks the mint, source and destination against the amounts the
+    /// steps moved
+    fn verify(&self) -> Result<(), String> {
+        verify_post_state(
+            &self.svm,
+            self.backend,
+            &[
+                Expect::Supply(self.mint, MINTED - TRANSFERRED),
+                Expect::Amount(self.source, MINTED - TRANSFERRED),
+                Expect::Delegate(self.source, None),
+                Expect::Closed(self.destination),
+            ],
+        )
+    }
+}
+
+pub fn benchmark_lifecycle() {
+    let (Some(token_ops_bytes), Some(token_ops_2022_bytes)) =
+        (load_program("token-ops"), load_program("token-ops-2022"))
+    else {
+        return;
+    };
+
+    let mut token = Lifecycle::new(&token_ops_bytes, Backend::Token);
+    let mut token_2022 = Lifecycle::new(&token_ops_2022_bytes, Backend::Token2022);
+    let token_results = token.run();
+    let token_2022_results = token_2022.run();
+
+    println!(
+        "{:<36} {:>10} {:>10} {:>10} {:>10}",
+        "Step", "Token", "Total", "T22", "Total"
+    );
+    println!("{}", "-".repeat(80));
+
+    let (mut token_total, mut token_2022_total) = (0u64, 0u64);
+    for (&(label, _, token_cu), &(_, _, token_2022_cu)) in
+        token_results.iter().zip(&token_2022_results)
+    {
+        token_total += token_cu;
+        token_2022_total += token_2022_cu;
+        println!(
+            "{:<36} {:>10} {:>10} {:>10} {:>10}",
+            label, token_cu, token_total, token_2022_cu, token_2022_total
+        );
+    }
+
+    for (name, lifecycle) in [("SPL Token", &token), ("Token-2022", &token_2022)] {
+        match lifecycle.verify() {
+            Ok(()) => println!("{}: final state verified", name),
+            Err(e) => panic!("{}: final state mismatch: {}", name, e),
+        }
+    }
+}
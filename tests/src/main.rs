@@ -1,17 +1,48 @@
-use litesvm::LiteSVM;
+mod authority;
+mod compliance;
+mod default_state;
+mod duplicates;
+mod extensions;
+mod fixtures;
+mod lifecycle;
+mod memo;
+mod mint_init;
+mod multisig;
+mod native;
+mod pda;
+mod return_data;
+mod scaling;
+mod scenario;
+mod script;
+mod transfer_fee;
+mod transfer_hook;
+mod ui_amount;
+mod verify;
+
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
 use solana_sdk::{
     account::Account,
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_pack::Pack,
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
+use extensions::ExtensionCase;
+use fixtures::{MintBuilder, TokenAccountBuilder};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use spl_token_2022::error::TokenError;
+use verify::{verify_post_state, Expect};
 
 const WRITE_LOOP_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x03; 32]);
 const WRITE_COPY_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x04; 32]);
 const TOKEN_OPS_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x05; 32]);
 const TOKEN_OPS_2022_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x06; 32]);
+const TRANSFER_HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0x07; 32]);
 
 // Token-2022 program ID (TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb)
 const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
@@ -31,8 +62,166 @@ fn main() {
     println!("\n=== Token CPI Benchmarks ===\n");
     benchmark_token_ops();
 
+    println!("\n=== Scripted Token CPI Benchmarks ===\n");
+    if let Some(token_ops_bytes) = load_program("token-ops") {
+        script::benchmark_scripts(&token_ops_bytes);
+    }
+
+    println!("\n=== Delegate and Close-Authority Token CPI Benchmarks ===\n");
+    authority::benchmark_alternate_authorities();
+
+    println!("\n=== Token Account Lifecycle Scenario ===\n");
+    lifecycle::benchmark_lifecycle();
+
+    println!("\n=== Multisig Token CPI Benchmarks ===\n");
+    if let Some(token_ops_bytes) = load_program("token-ops") {
+        multisig::benchmark_multisig(&token_ops_bytes);
+    }
+
+    println!("\n=== PDA-Signed Token CPI Benchmarks ===\n");
+    if let Some(token_ops_bytes) = load_program("token-ops") {
+        pda::benchmark_pda_signed(&token_ops_bytes);
+    }
+
     println!("\n=== Token-2022 CPI Benchmarks ===\n");
     benchmark_token_2022_ops();
+
+    println!("\n=== Token-2022 Extension Fixtures ===\n");
+    extensions::report_extension_fixtures();
+
+    println!("\n=== Token-2022 Extension CU Surcharge ===\n");
+    extensions::benchmark_extension_surcharges();
+
+    println!("\n=== Transfer-Fee Extension Benchmarks (Token-2022 CPI) ===\n");
+    transfer_fee::benchmark_transfer_fee();
+
+    println!("\n=== Transfer-Hook Extension Benchmarks (Token-2022 CPI) ===\n");
+    transfer_hook::benchmark_transfer_hook();
+
+    println!("\n=== Mint Creation with Extensions (Token-2022 CPI) ===\n");
+    mint_init::benchmark_mint_init();
+
+    println!("\n=== Memo Benchmarks (Token CPI) ===\n");
+    memo::benchmark_memo();
+
+    println!("\n=== Memo-Required Transfer Benchmarks (Token-2022 CPI) ===\n");
+    memo::benchmark_memo_transfer();
+
+    println!("\n=== UI Amount Extension Benchmarks (Token-2022 CPI) ===\n");
+    ui_amount::benchmark_ui_amount();
+
+    println!("\n=== Default Account State Benchmarks (Token-2022 CPI) ===\n");
+    default_state::benchmark_default_state();
+
+    println!("\n=== Permanent Delegate Benchmarks (Token-2022 CPI) ===\n");
+    compliance::benchmark_permanent_delegate();
+
+    println!("\n=== Pausable Mint Benchmarks (Token-2022 CPI) ===\n");
+    compliance::benchmark_pausable();
+
+    println!("\n=== Wrapped SOL (Native Mint) Benchmarks ===\n");
+    native::benchmark_native();
+
+    println!("\n=== Return-Data Query Benchmarks ===\n");
+    return_data::benchmark_return_data();
+
+    println!("\n=== Account-Count Scaling (entrypoint + CPI) ===\n");
+    scaling::benchmark_account_count();
+
+    println!("\n=== Duplicate-Account Benchmarks ===\n");
+    duplicates::benchmark_duplicate_accounts();
+
+    println!("\n=== Account-Data-Size Scaling (Token-2022 CPI) ===\n");
+    scaling::benchmark_account_data_size();
+
+    println!("\n=== Instruction-Data Length Scaling (outer + CPI) ===\n");
+    scaling::benchmark_instruction_data_len();
+}
+
+/// Token program a benchmark runs against, through its token-ops program
+#[derive(Clone, Copy)]
+enum Backend {
+    Token,
+    Token2022,
+}
+
+impl Backend {
+    /// The token program itself
+    fn token_program(self) -> Pubkey {
+        match self {
+            Backend::Token => spl_token::ID,
+            Backend::Token2022 => TOKEN_2022_PROGRAM_ID,
+        }
+    }
+
+    /// The token-ops program that CPIs into it
+    fn token_ops(self) -> Pubkey {
+        match self {
+            Backend::Token => TOKEN_OPS_PROGRAM_ID,
+            Backend::Token2022 => TOKEN_OPS_2022_PROGRAM_ID,
+        }
+    }
+
+    /// ELF of the token program
+    fn program_bytes(self) -> &'static [u8] {
+        match self {
+            Backend::Token => include_bytes!("spl_token.so"),
+            Backend::Token2022 => include_bytes!("spl_token_2022.so"),
+        }
+    }
+}
+
+/// Reads a benchmark program's ELF from target/deploy
+fn load_program(program_name: &str) -> Option<Vec<u8>> {
+    let program_path = format!("target/deploy/{}.so", program_name.replace('-', "_"));
+    match std::fs::read(&program_path) {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", program_path, e);
+            eprintln!(
+                "Make sure to build with: cargo build-sbf --manifest-path programs/{}/Cargo.toml",
+                program_name
+            );
+            None
+        }
+    }
+}
+
+/// Account holding fixture `data`, owned by `owner` and funded well above rent
+fn fixture_account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Sends `instructions` as one transaction paid by `payer` and signed by `signers`
+fn send_instructions(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map_err(Box::new)
+}
+
+/// Whether a transaction failed because the token program returned `error`
+fn is_token_error(e: &FailedTransactionMetadata, error: TokenError) -> bool {
+    matches!(
+        e.err,
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) if code == error as u32
+    )
 }
 
 fn benchmark_write_bytes() {
@@ -120,73 +309,34 @@ fn benchmark_token_ops() {
     };
 
     println!(
-        "{:<25} {:>12}",
+        "{:<45} {:>12}",
         "Operation", "CU Consumed"
     );
-    println!("{}", "-".repeat(38));
-
-    // Benchmark Transfer
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::Transfer);
-    println!("{:<25} {:>12}", "Transfer", cu);
-
-    // Benchmark TransferChecked
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::TransferChecked);
-    println!("{:<25} {:>12}", "TransferChecked", cu);
-
-    // Benchmark MintTo
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::MintTo);
-    println!("{:<25} {:>12}", "MintTo", cu);
-
-    // Benchmark Burn
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::Burn);
-    println!("{:<25} {:>12}", "Burn", cu);
-
-    // Benchmark Approve
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::Approve);
-    println!("{:<25} {:>12}", "Approve", cu);
-
-    // Benchmark Revoke
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::Revoke);
-    println!("{:<25} {:>12}", "Revoke", cu);
-
-    // Benchmark FreezeAccount
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::FreezeAccount);
-    println!("{:<25} {:>12}", "FreezeAccount", cu);
-
-    // Benchmark ThawAccount
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::ThawAccount);
-    println!("{:<25} {:>12}", "ThawAccount", cu);
-
-    // Benchmark CloseAccount
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::CloseAccount);
-    println!("{:<25} {:>12}", "CloseAccount", cu);
+    println!("{}", "-".repeat(58));
 
-    // Benchmark InitializeMint
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::InitializeMint);
-    println!("{:<25} {:>12}", "InitializeMint", cu);
-
-    // Benchmark InitializeMint2
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::InitializeMint2);
-    println!("{:<25} {:>12}", "InitializeMint2", cu);
-
-    // Benchmark InitializeAccount
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::InitializeAccount);
-    println!("{:<25} {:>12}", "InitializeAccount", cu);
-
-    // Benchmark InitializeAccount2
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::InitializeAccount2);
-    println!("{:<25} {:>12}", "InitializeAccount2", cu);
-
-    // Benchmark InitializeAccount3
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::InitializeAccount3);
-    println!("{:<25} {:>12}", "InitializeAccount3", cu);
+    // Benchmark every op, with its argument variants grouped under it
+    for &(op, label) in BASE_TOKEN_OPS {
+        for &variant in op.variants() {
+            let cu = run_token_benchmark(&token_ops_bytes, op, variant);
+            print_variant_row(label, variant, cu);
+        }
+    }
 
-    // Benchmark SetAuthority
-    let cu = run_token_benchmark(&token_ops_bytes, TokenOp::SetAuthority);
-    println!("{:<25} {:>12}", "SetAuthority", cu);
+    // Benchmark SetAuthority for every authority type, and its removal,
+    // plus one token-ops must reject
+    for &(authority_type, name) in TOKEN_AUTHORITY_TYPES {
+        let op = TokenOp::SetAuthority(authority_type);
+        for &variant in op.variants() {
+            let cu = run_token_benchmark(&token_ops_bytes, op, variant);
+            print_variant_row(&format!("SetAuthority({})", name), variant, cu);
+        }
+    }
+    let op = TokenOp::SetAuthority(INVALID_AUTHORITY_TYPE);
+    let cu = run_token_benchmark(&token_ops_bytes, op, OpVariant::Default);
+    println!("{:<45} {:>12}", "SetAuthority(invalid, rejected)", cu);
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 enum TokenOp {
     Transfer,
     MintTo,
@@ -202,10 +352,152 @@ enum TokenOp {
     InitializeAccount,
     InitializeAccount2,
     InitializeAccount3,
-    SetAuthority,
+    SetAuthority(u8),
+    MintToChecked,
+    BurnChecked,
+    ApproveChecked,
+}
+
+impl TokenOp {
+    /// Argument variants benchmarked for the op, starting with the default set
+    fn variants(self) -> &'static [OpVariant] {
+        use OpVariant::*;
+        match self {
+            TokenOp::Transfer | TokenOp::TransferChecked => {
+                &[Default, ZeroAmount, FullBalance, SelfTransfer]
+            }
+            TokenOp::MintTo | TokenOp::MintToChecked => &[Default, ZeroAmount],
+            TokenOp::Burn | TokenOp::BurnChecked | TokenOp::Approve | TokenOp::ApproveChecked => {
+                &[Default, ZeroAmount, FullBalance]
+            }
+            TokenOp::CloseAccount => &[Default, CloseToSelf],
+            TokenOp::InitializeMint | TokenOp::InitializeMint2 => &[Default, NoFreezeAuthority],
+            TokenOp::SetAuthority(INVALID_AUTHORITY_TYPE) => &[Default],
+            TokenOp::SetAuthority(_) => &[Default, RemoveAuthority],
+            _ => &[Default],
+        }
+    }
+
+    /// Whether the token program (or token-ops, before any CPI) is expected
+    /// to reject the instruction
+    fn expects_failure(self, variant: OpVariant) -> bool {
+        match (self, variant) {
+            (TokenOp::SetAuthority(INVALID_AUTHORITY_TYPE), _) => true,
+            // Token accounts always need an owner
+            (TokenOp::SetAuthority(2), OpVariant::RemoveAuthority) => true,
+            // The destination must differ from the account being closed
+            (TokenOp::CloseAccount, OpVariant::CloseToSelf) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Ops benchmarked against both token programs, in report order. SetAuthority
+/// is benchmarked separately, once per authority type.
+const BASE_TOKEN_OPS: &[(TokenOp, &str)] = &[
+    (TokenOp::Transfer, "Transfer"),
+    (TokenOp::TransferChecked, "TransferChecked"),
+    (TokenOp::MintTo, "MintTo"),
+    (TokenOp::MintToChecked, "MintToChecked"),
+    (TokenOp::Burn, "Burn"),
+    (TokenOp::BurnChecked, "BurnChecked"),
+    (TokenOp::Approve, "Approve"),
+    (TokenOp::ApproveChecked, "ApproveChecked"),
+    (TokenOp::Revoke, "Revoke"),
+    (TokenOp::FreezeAccount, "FreezeAccount"),
+    (TokenOp::ThawAccount, "ThawAccount"),
+    (TokenOp::CloseAccount, "CloseAccount"),
+    (TokenOp::InitializeMint, "InitializeMint"),
+    (TokenOp::InitializeMint2, "InitializeMint2"),
+    (TokenOp::InitializeAccount, "InitializeAccount"),
+    (TokenOp::InitializeAccount2, "InitializeAccount2"),
+    (TokenOp::InitializeAccount3, "InitializeAccount3"),
+];
+
+/// Argument set an op is benchmarked with
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OpVariant {
+    /// Amount 1000, decimals 9, freeze authority set, a new authority
+    Default,
+    ZeroAmount,
+    /// The source's entire balance
+    FullBalance,
+    /// Source and destination are the same account
+    SelfTransfer,
+    /// InitializeMint without a freeze authority
+    NoFreezeAuthority,
+    /// SetAuthority to None
+    RemoveAuthority,
+    /// CloseAccount with the closed account as the destination
+    CloseToSelf,
 }
 
-fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
+impl OpVariant {
+    /// Token amount the variant passes to amount-taking ops
+    fn amount(self) -> u64 {
+        match self {
+            OpVariant::ZeroAmount => 0,
+            OpVariant::FullBalance => FIXTURE_BALANCE,
+            _ => 1000,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            OpVariant::Default => "default",
+            OpVariant::ZeroAmount => "zero amount",
+            OpVariant::FullBalance => "full balance",
+            OpVariant::SelfTransfer => "self-transfer",
+            OpVariant::NoFreezeAuthority => "no freeze authority",
+            OpVariant::RemoveAuthority => "remove authority (None)",
+            OpVariant::CloseToSelf => "close to self (rejected)",
+        }
+    }
+}
+
+/// Balance of the fixture source token account, and the fixture mint's supply
+const FIXTURE_BALANCE: u64 = 1_000_000_000;
+
+/// Prints a benchmark row: the default variant under the op's own label,
+/// every other variant indented beneath it
+fn print_variant_row(label: &str, variant: OpVariant, cu: u64) {
+    if variant == OpVariant::Default {
+        println!("{:<45} {:>12}", label, cu);
+    } else {
+        println!("{:<45} {:>12}", format!("  {}", variant.label()), cu);
+    }
+}
+
+/// SetAuthority authority types shared by SPL Token and Token-2022
+const TOKEN_AUTHORITY_TYPES: &[(u8, &str)] = &[
+    (0, "MintTokens"),
+    (1, "FreezeAccount"),
+    (2, "AccountOwner"),
+    (3, "CloseAccount"),
+];
+
+/// SetAuthority authority types only Token-2022 defines. Each runs against a
+/// mint carrying the extension the authority belongs to.
+const TOKEN_2022_EXTENSION_AUTHORITY_TYPES: &[(u8, &str)] = &[
+    (4, "TransferFeeConfig"),
+    (5, "WithheldWithdraw"),
+    (6, "CloseMint"),
+    (7, "InterestRate"),
+    (8, "PermanentDelegate"),
+    (9, "ConfidentialTransferMint"),
+    (10, "TransferHookProgramId"),
+    (11, "ConfidentialTransferFeeConfig"),
+    (12, "MetadataPointer"),
+    (13, "GroupPointer"),
+    (14, "GroupMemberPointer"),
+    (15, "ScaledUiAmount"),
+    (16, "Pause"),
+];
+
+/// Authority type byte neither token program defines
+const INVALID_AUTHORITY_TYPE: u8 = 0xff;
+
+fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp, variant: OpVariant) -> u64 {
     let mut svm = LiteSVM::new();
 
     // Add SPL Token program
@@ -221,77 +513,81 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
 
     // Create mint account (supply matches source token account balance)
     let mint = Pubkey::new_unique();
-    let mint_data = create_mint_data(&authority.pubkey(), Some(&authority.pubkey()), 9, 1_000_000_000);
-    svm.set_account(
-        mint,
-        Account {
-            lamports: 1_000_000_000,
-            data: mint_data,
-            owner: spl_token::ID,
-            executable: false,
-            rent_epoch: 0,
-        },
-    )
-    .unwrap();
+    let mint_data = MintBuilder::new(&authority.pubkey())
+        .freeze_authority(&authority.pubkey())
+        .supply(1_000_000_000)
+        .build();
+    svm.set_account(mint, fixture_account(spl_token::ID, mint_data))
+        .unwrap();
 
     // Create source token account
     let source_token = Pubkey::new_unique();
-    let source_data = create_token_account_data(&mint, &authority.pubkey(), 1_000_000_000);
-    svm.set_account(
-        source_token,
-        Account {
-            lamports: 1_000_000_000,
-            data: source_data,
-            owner: spl_token::ID,
-            executable: false,
-            rent_epoch: 0,
-        },
-    )
-    .unwrap();
+    let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .amount(FIXTURE_BALANCE)
+        .build();
+    svm.set_account(source_token, fixture_account(spl_token::ID, source_data))
+        .unwrap();
 
     // Create destination token account
     let dest_token = Pubkey::new_unique();
-    let dest_data = create_token_account_data(&mint, &authority.pubkey(), 0);
-    svm.set_account(
-        dest_token,
-        Account {
-            lamports: 1_000_000_000,
-            data: dest_data,
-            owner: spl_token::ID,
-            executable: false,
-            rent_epoch: 0,
-        },
-    )
-    .unwrap();
+    let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build();
+    svm.set_account(dest_token, fixture_account(spl_token::ID, dest_data))
+        .unwrap();
 
     // Create delegate account (for approve/revoke)
     let delegate = Pubkey::new_unique();
 
+    // Arguments the variant changes
+    let amount = variant.amount();
+    let transfer_dest = if variant == OpVariant::SelfTransfer {
+        source_token
+    } else {
+        dest_token
+    };
+    let has_freeze_authority = (variant != OpVariant::NoFreezeAuthority) as u8;
+    let has_new_authority = (variant != OpVariant::RemoveAuthority) as u8;
+
     // Build instruction based on operation
-    let (accounts, data, needs_authority_signer) = match op {
+    let (accounts, data, needs_authority_signer, expected) = match op {
         TokenOp::Transfer => {
             let accounts = vec![
                 AccountMeta::new(source_token, false),
-                AccountMeta::new(dest_token, false),
+                AccountMeta::new(transfer_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let mut data = vec![0u8]; // discriminator for Transfer
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = if transfer_dest == source_token {
+                vec![Expect::Amount(source_token, FIXTURE_BALANCE)]
+            } else {
+                vec![
+                    Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+                    Expect::Amount(dest_token, amount),
+                ]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::TransferChecked => {
             let accounts = vec![
                 AccountMeta::new(source_token, false),
                 AccountMeta::new_readonly(mint, false),
-                AccountMeta::new(dest_token, false),
+                AccountMeta::new(transfer_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let mut data = vec![8u8]; // discriminator for TransferChecked
-            data.extend_from_slice(&1000u64.to_le_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
             data.push(9); // decimals
-            (accounts, data, true)
+            let expected = if transfer_dest == source_token {
+                vec![Expect::Amount(source_token, FIXTURE_BALANCE)]
+            } else {
+                vec![
+                    Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+                    Expect::Amount(dest_token, amount),
+                ]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::MintTo => {
             let accounts = vec![
@@ -301,8 +597,28 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let mut data = vec![1u8]; // discriminator for MintTo
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE + amount),
+                Expect::Amount(dest_token, amount),
+            ];
+            (accounts, data, true, expected)
+        }
+        TokenOp::MintToChecked => {
+            let accounts = vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ];
+            let mut data = vec![18u8]; // discriminator for MintToChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE + amount),
+                Expect::Amount(dest_token, amount),
+            ];
+            (accounts, data, true, expected)
         }
         TokenOp::Burn => {
             let accounts = vec![
@@ -312,8 +628,28 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let mut data = vec![2u8]; // discriminator for Burn
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE - amount),
+                Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+            ];
+            (accounts, data, true, expected)
+        }
+        TokenOp::BurnChecked => {
+            let accounts = vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ];
+            let mut data = vec![19u8]; // discriminator for BurnChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE - amount),
+                Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+            ];
+            (accounts, data, true, expected)
         }
         TokenOp::Approve => {
             let accounts = vec![
@@ -323,8 +659,23 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let mut data = vec![3u8]; // discriminator for Approve
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![Expect::Delegate(source_token, Some((delegate, amount)))];
+            (accounts, data, true, expected)
+        }
+        TokenOp::ApproveChecked => {
+            let accounts = vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(delegate, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ];
+            let mut data = vec![20u8]; // discriminator for ApproveChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![Expect::Delegate(source_token, Some((delegate, amount)))];
+            (accounts, data, true, expected)
         }
         TokenOp::Revoke => {
             // First approve a delegate, then revoke
@@ -350,7 +701,7 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                     &[&payer, &authority],
                     blockhash,
                 );
-                let _ = svm.send_transaction(tx);
+                svm.send_transaction(tx).expect("Approve before Revoke failed");
             }
 
             let accounts = vec![
@@ -359,7 +710,8 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![4u8]; // discriminator for Revoke
-            (accounts, data, true)
+            let expected = vec![Expect::Delegate(source_token, None)];
+            (accounts, data, true, expected)
         }
         TokenOp::FreezeAccount => {
             let accounts = vec![
@@ -369,7 +721,8 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![6u8]; // discriminator for FreezeAccount
-            (accounts, data, true)
+            let expected = vec![Expect::State(source_token, AccountState::Frozen)];
+            (accounts, data, true, expected)
         }
         TokenOp::ThawAccount => {
             // First freeze the account
@@ -394,7 +747,7 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                     &[&payer, &authority],
                     blockhash,
                 );
-                let _ = svm.send_transaction(tx);
+                svm.send_transaction(tx).expect("FreezeAccount before ThawAccount failed");
             }
 
             let accounts = vec![
@@ -404,94 +757,95 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![7u8]; // discriminator for ThawAccount
-            (accounts, data, true)
+            let expected = vec![Expect::State(source_token, AccountState::Initialized)];
+            (accounts, data, true, expected)
         }
         TokenOp::CloseAccount => {
             // Create a fresh token account with zero balance for closing
             let close_token = Pubkey::new_unique();
-            let close_data = create_token_account_data(&mint, &authority.pubkey(), 0);
-            svm.set_account(
-                close_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: close_data,
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
-            )
-            .unwrap();
-
+            let close_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build();
+            svm.set_account(close_token, fixture_account(spl_token::ID, close_data))
+                .unwrap();
+
+            let close_dest = if variant == OpVariant::CloseToSelf {
+                close_token
+            } else {
+                authority.pubkey()
+            };
             let accounts = vec![
                 AccountMeta::new(close_token, false),
-                AccountMeta::new(authority.pubkey(), false),
+                AccountMeta::new(close_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![5u8]; // discriminator for CloseAccount
-            (accounts, data, true)
+            // Closing into itself is rejected, leaving the account open
+            let expected = if close_dest == close_token {
+                vec![Expect::Amount(close_token, 0)]
+            } else {
+                vec![Expect::Closed(close_token)]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::InitializeMint => {
             // Create uninitialized mint account
             let new_mint = Pubkey::new_unique();
             svm.set_account(
                 new_mint,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 82], // Mint size
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(spl_token::ID, vec![0u8; Mint::LEN]),
             )
             .unwrap();
 
-            let accounts = vec![
+            let mut accounts = vec![
                 AccountMeta::new(new_mint, false),
                 AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
                 AccountMeta::new_readonly(authority.pubkey(), false),
-                AccountMeta::new_readonly(authority.pubkey(), false), // freeze authority
-                AccountMeta::new_readonly(spl_token::ID, false),
             ];
-            let data = vec![9u8, 9, 1]; // discriminator, decimals, has_freeze_authority
-            (accounts, data, false) // no authority signer needed
+            if has_freeze_authority != 0 {
+                accounts.push(AccountMeta::new_readonly(authority.pubkey(), false)); // freeze authority
+            }
+            accounts.push(AccountMeta::new_readonly(spl_token::ID, false));
+            let data = vec![9u8, 9, has_freeze_authority]; // discriminator, decimals, has_freeze_authority
+            let freeze_authority = (has_freeze_authority != 0).then_some(authority.pubkey());
+            let expected = vec![
+                Expect::MintAuthority(new_mint, Some(authority.pubkey())),
+                Expect::FreezeAuthority(new_mint, freeze_authority),
+                Expect::Supply(new_mint, 0),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeMint2 => {
             // Create uninitialized mint account
             let new_mint = Pubkey::new_unique();
             svm.set_account(
                 new_mint,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 82], // Mint size
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(spl_token::ID, vec![0u8; Mint::LEN]),
             )
             .unwrap();
 
-            let accounts = vec![
+            let mut accounts = vec![
                 AccountMeta::new(new_mint, false),
                 AccountMeta::new_readonly(authority.pubkey(), false),
-                AccountMeta::new_readonly(authority.pubkey(), false), // freeze authority
-                AccountMeta::new_readonly(spl_token::ID, false),
             ];
-            let data = vec![10u8, 9, 1]; // discriminator, decimals, has_freeze_authority
-            (accounts, data, false) // no authority signer needed
+            if has_freeze_authority != 0 {
+                accounts.push(AccountMeta::new_readonly(authority.pubkey(), false)); // freeze authority
+            }
+            accounts.push(AccountMeta::new_readonly(spl_token::ID, false));
+            let data = vec![10u8, 9, has_freeze_authority]; // discriminator, decimals, has_freeze_authority
+            let freeze_authority = (has_freeze_authority != 0).then_some(authority.pubkey());
+            let expected = vec![
+                Expect::MintAuthority(new_mint, Some(authority.pubkey())),
+                Expect::FreezeAuthority(new_mint, freeze_authority),
+                Expect::Supply(new_mint, 0),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(spl_token::ID, vec![0u8; TokenAccount::LEN]),
             )
             .unwrap();
 
@@ -503,20 +857,19 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![11u8]; // discriminator for InitializeAccount
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount2 => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(spl_token::ID, vec![0u8; TokenAccount::LEN]),
             )
             .unwrap();
 
@@ -528,20 +881,19 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![12u8]; // discriminator for InitializeAccount2
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount3 => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: spl_token::ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(spl_token::ID, vec![0u8; TokenAccount::LEN]),
             )
             .unwrap();
 
@@ -552,18 +904,43 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
             let data = vec![13u8]; // discriminator for InitializeAccount3
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
-        TokenOp::SetAuthority => {
+        TokenOp::SetAuthority(authority_type) => {
+            // AccountOwner and CloseAccount live on the token account, everything else on the mint
+            let target = match authority_type {
+                2 | 3 => source_token,
+                _ => mint,
+            };
             let new_authority = Pubkey::new_unique();
             let accounts = vec![
-                AccountMeta::new(mint, false),
+                AccountMeta::new(target, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(new_authority, false), // new authority
                 AccountMeta::new_readonly(spl_token::ID, false),
             ];
-            let data = vec![14u8, 0, 1]; // discriminator, authority_type (MintTokens=0), has_new_authority
-            (accounts, data, true) // authority signer needed
+            let data = vec![14u8, authority_type, has_new_authority]; // discriminator, authority_type, has_new_authority
+            // A rejected SetAuthority leaves the current authority in place
+            let expected_authority = if op.expects_failure(variant) {
+                Some(authority.pubkey())
+            } else {
+                (has_new_authority != 0).then_some(new_authority)
+            };
+            let expected = match (authority_type, expected_authority) {
+                (0 | INVALID_AUTHORITY_TYPE, _) => {
+                    vec![Expect::MintAuthority(mint, expected_authority)]
+                }
+                (1, _) => vec![Expect::FreezeAuthority(mint, expected_authority)],
+                (2, Some(owner)) => vec![Expect::Owner(source_token, owner)],
+                (3, _) => vec![Expect::CloseAuthority(source_token, expected_authority)],
+                _ => vec![Expect::ExtensionAuthority(mint, authority_type, expected_authority)],
+            };
+            (accounts, data, true, expected) // authority signer needed
         }
     };
 
@@ -590,13 +967,18 @@ fn run_token_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
         )
     };
 
-    match svm.send_transaction(tx) {
-        Ok(tx_result) => tx_result.compute_units_consumed,
-        Err(e) => {
-            eprintln!("Transaction failed for {:?}: {:?}", op as u8, e);
-            e.meta.compute_units_consumed
-        }
+    let expects_failure = op.expects_failure(variant);
+    let cu = match svm.send_transaction(tx) {
+        Ok(tx_result) if !expects_failure => tx_result.compute_units_consumed,
+        Ok(_) => panic!("Transaction unexpectedly succeeded for {:?} ({:?})", op, variant),
+        Err(e) if expects_failure => e.meta.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {:?} ({:?}): {:?}", op, variant, e),
+    };
+
+    if let Err(e) = verify_post_state(&svm, Backend::Token, &expected) {
+        panic!("Post-state mismatch for {:?} ({:?}): {}", op, variant, e);
     }
+    cu
 }
 
 fn benchmark_token_2022_ops() {
@@ -611,73 +993,60 @@ fn benchmark_token_2022_ops() {
     };
 
     println!(
-        "{:<25} {:>12}",
+        "{:<45} {:>12}",
         "Operation", "CU Consumed"
     );
-    println!("{}", "-".repeat(38));
-
-    // Benchmark Transfer
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::Transfer);
-    println!("{:<25} {:>12}", "Transfer", cu);
-
-    // Benchmark TransferChecked
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::TransferChecked);
-    println!("{:<25} {:>12}", "TransferChecked", cu);
-
-    // Benchmark MintTo
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::MintTo);
-    println!("{:<25} {:>12}", "MintTo", cu);
-
-    // Benchmark Burn
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::Burn);
-    println!("{:<25} {:>12}", "Burn", cu);
-
-    // Benchmark Approve
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::Approve);
-    println!("{:<25} {:>12}", "Approve", cu);
-
-    // Benchmark Revoke
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::Revoke);
-    println!("{:<25} {:>12}", "Revoke", cu);
-
-    // Benchmark FreezeAccount
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::FreezeAccount);
-    println!("{:<25} {:>12}", "FreezeAccount", cu);
-
-    // Benchmark ThawAccount
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::ThawAccount);
-    println!("{:<25} {:>12}", "ThawAccount", cu);
-
-    // Benchmark CloseAccount
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::CloseAccount);
-    println!("{:<25} {:>12}", "CloseAccount", cu);
-
-    // Benchmark InitializeMint
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::InitializeMint);
-    println!("{:<25} {:>12}", "InitializeMint", cu);
-
-    // Benchmark InitializeMint2
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::InitializeMint2);
-    println!("{:<25} {:>12}", "InitializeMint2", cu);
-
-    // Benchmark InitializeAccount
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::InitializeAccount);
-    println!("{:<25} {:>12}", "InitializeAccount", cu);
-
-    // Benchmark InitializeAccount2
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::InitializeAccount2);
-    println!("{:<25} {:>12}", "InitializeAccount2", cu);
-
-    // Benchmark InitializeAccount3
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::InitializeAccount3);
-    println!("{:<25} {:>12}", "InitializeAccount3", cu);
+    println!("{}", "-".repeat(58));
+
+    // Benchmark every op, with its argument variants grouped under it
+    for &(op, label) in BASE_TOKEN_OPS {
+        for &variant in op.variants() {
+            let cu = run_token_2022_benchmark(
+                &token_ops_2022_bytes,
+                op,
+                variant,
+                &Keypair::new(),
+                &ExtensionCase::plain(),
+            );
+            print_variant_row(label, variant, cu);
+        }
+    }
 
-    // Benchmark SetAuthority
-    let cu = run_token_2022_benchmark(&token_ops_2022_bytes, TokenOp::SetAuthority);
-    println!("{:<25} {:>12}", "SetAuthority", cu);
+    // Benchmark SetAuthority for every authority type, and its removal,
+    // plus one token-ops-2022 must reject
+    for &(authority_type, name) in TOKEN_AUTHORITY_TYPES
+        .iter()
+        .chain(TOKEN_2022_EXTENSION_AUTHORITY_TYPES) {
+        let op = TokenOp::SetAuthority(authority_type);
+        for &variant in op.variants() {
+            // Extension authority types need the mint to carry the extension
+            let authority = Keypair::new();
+            let case = extensions::authority_type_case(authority_type, &authority.pubkey());
+            let cu = run_token_2022_benchmark(&token_ops_2022_bytes, op, variant, &authority, &case);
+            print_variant_row(&format!("SetAuthority({})", name), variant, cu);
+        }
+    }
+    let op = TokenOp::SetAuthority(INVALID_AUTHORITY_TYPE);
+    let cu = run_token_2022_benchmark(
+        &token_ops_2022_bytes,
+        op,
+        OpVariant::Default,
+        &Keypair::new(),
+        &ExtensionCase::plain(),
+    );
+    println!("{:<45} {:>12}", "SetAuthority(invalid, rejected)", cu);
 }
 
-fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
+/// Runs `op` through token-ops-2022, with `authority` owning the fixture
+/// accounts and holding every mint authority, and the fixture mint and token
+/// accounts carrying the `extensions` case
+fn run_token_2022_benchmark(
+    token_ops_bytes: &[u8],
+    op: TokenOp,
+    variant: OpVariant,
+    authority: &Keypair,
+    extensions: &ExtensionCase,
+) -> u64 {
     let mut svm = LiteSVM::new();
 
     // Add SPL Token-2022 program
@@ -687,83 +1056,97 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
     svm.add_program(TOKEN_OPS_2022_PROGRAM_ID, token_ops_bytes);
 
     let payer = Keypair::new();
-    let authority = Keypair::new();
     svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
     svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
 
+    // Token-2022 rejects some ops outright on some extensions
+    let expects_failure = op.expects_failure(variant) || extensions.rejects(op);
+
     // Create mint account (supply matches source token account balance)
     let mint = Pubkey::new_unique();
-    let mint_data = create_mint_data(&authority.pubkey(), Some(&authority.pubkey()), 9, 1_000_000_000);
-    svm.set_account(
-        mint,
-        Account {
-            lamports: 1_000_000_000,
-            data: mint_data,
-            owner: TOKEN_2022_PROGRAM_ID,
-            executable: false,
-            rent_epoch: 0,
-        },
-    )
-    .unwrap();
+    let mint_data = MintBuilder::new(&authority.pubkey())
+        .freeze_authority(&authority.pubkey())
+        .supply(1_000_000_000)
+        .build_token_2022(&extensions.mint_extensions);
+    svm.set_account(mint, fixture_account(TOKEN_2022_PROGRAM_ID, mint_data))
+        .unwrap();
 
     // Create source token account
     let source_token = Pubkey::new_unique();
-    let source_data = create_token_account_data(&mint, &authority.pubkey(), 1_000_000_000);
+    let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .amount(FIXTURE_BALANCE)
+        .build_token_2022(&extensions.account_extensions);
     svm.set_account(
         source_token,
-        Account {
-            lamports: 1_000_000_000,
-            data: source_data,
-            owner: TOKEN_2022_PROGRAM_ID,
-            executable: false,
-            rent_epoch: 0,
-        },
+        fixture_account(TOKEN_2022_PROGRAM_ID, source_data),
     )
     .unwrap();
 
     // Create destination token account
     let dest_token = Pubkey::new_unique();
-    let dest_data = create_token_account_data(&mint, &authority.pubkey(), 0);
+    let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build_token_2022(&extensions.account_extensions);
     svm.set_account(
         dest_token,
-        Account {
-            lamports: 1_000_000_000,
-            data: dest_data,
-            owner: TOKEN_2022_PROGRAM_ID,
-            executable: false,
-            rent_epoch: 0,
-        },
+        fixture_account(TOKEN_2022_PROGRAM_ID, dest_data),
     )
     .unwrap();
 
     // Create delegate account (for approve/revoke)
     let delegate = Pubkey::new_unique();
 
+    // Arguments the variant changes
+    let amount = variant.amount();
+    let transfer_dest = if variant == OpVariant::SelfTransfer {
+        source_token
+    } else {
+        dest_token
+    };
+    let has_freeze_authority = (variant != OpVariant::NoFreezeAuthority) as u8;
+    let has_new_authority = (variant != OpVariant::RemoveAuthority) as u8;
+
     // Build instruction based on operation
-    let (accounts, data, needs_authority_signer) = match op {
+    let (accounts, data, needs_authority_signer, expected) = match op {
         TokenOp::Transfer => {
             let accounts = vec![
                 AccountMeta::new(source_token, false),
-                AccountMeta::new(dest_token, false),
+                AccountMeta::new(transfer_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let mut data = vec![0u8]; // discriminator for Transfer
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = if transfer_dest == source_token {
+                vec![Expect::Amount(source_token, FIXTURE_BALANCE)]
+            } else {
+                vec![
+                    Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+                    Expect::Amount(dest_token, amount),
+                ]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::TransferChecked => {
             let accounts = vec![
                 AccountMeta::new(source_token, false),
                 AccountMeta::new_readonly(mint, false),
-                AccountMeta::new(dest_token, false),
+                AccountMeta::new(transfer_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let mut data = vec![8u8]; // discriminator for TransferChecked
-            data.extend_from_slice(&1000u64.to_le_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
             data.push(9); // decimals
-            (accounts, data, true)
+            let expected = if transfer_dest == source_token {
+                vec![Expect::Amount(source_token, FIXTURE_BALANCE)]
+            } else {
+                // A transfer fee is withheld in the destination
+                let fee = extensions.transfer_fee(amount);
+                vec![
+                    Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+                    Expect::Amount(dest_token, amount - fee),
+                ]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::MintTo => {
             let accounts = vec![
@@ -773,8 +1156,28 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let mut data = vec![1u8]; // discriminator for MintTo
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE + amount),
+                Expect::Amount(dest_token, amount),
+            ];
+            (accounts, data, true, expected)
+        }
+        TokenOp::MintToChecked => {
+            let accounts = vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ];
+            let mut data = vec![18u8]; // discriminator for MintToChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE + amount),
+                Expect::Amount(dest_token, amount),
+            ];
+            (accounts, data, true, expected)
         }
         TokenOp::Burn => {
             let accounts = vec![
@@ -784,8 +1187,28 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let mut data = vec![2u8]; // discriminator for Burn
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE - amount),
+                Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+            ];
+            (accounts, data, true, expected)
+        }
+        TokenOp::BurnChecked => {
+            let accounts = vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ];
+            let mut data = vec![19u8]; // discriminator for BurnChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![
+                Expect::Supply(mint, FIXTURE_BALANCE - amount),
+                Expect::Amount(source_token, FIXTURE_BALANCE - amount),
+            ];
+            (accounts, data, true, expected)
         }
         TokenOp::Approve => {
             let accounts = vec![
@@ -795,23 +1218,38 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let mut data = vec![3u8]; // discriminator for Approve
-            data.extend_from_slice(&1000u64.to_le_bytes());
-            (accounts, data, true)
+            data.extend_from_slice(&amount.to_le_bytes());
+            let expected = vec![Expect::Delegate(source_token, Some((delegate, amount)))];
+            (accounts, data, true, expected)
+        }
+        TokenOp::ApproveChecked => {
+            let accounts = vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(delegate, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ];
+            let mut data = vec![20u8]; // discriminator for ApproveChecked
+            data.extend_from_slice(&amount.to_le_bytes());
+            data.push(9); // decimals
+            let expected = vec![Expect::Delegate(source_token, Some((delegate, amount)))];
+            (accounts, data, true, expected)
         }
         TokenOp::Revoke => {
-            // First approve a delegate, then revoke
+            // First approve a delegate, then revoke. The approval goes straight
+            // to Token-2022 since CPI Guard rejects approving through a CPI.
             {
                 let approve_accounts = vec![
                     AccountMeta::new(source_token, false),
                     AccountMeta::new_readonly(delegate, false),
                     AccountMeta::new_readonly(authority.pubkey(), true),
-                    AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
                 ];
-                let mut approve_data = vec![3u8];
+                let mut approve_data = vec![4u8]; // discriminator for Token-2022 Approve
                 approve_data.extend_from_slice(&1000u64.to_le_bytes());
 
                 let instruction = Instruction {
-                    program_id: TOKEN_OPS_2022_PROGRAM_ID,
+                    program_id: TOKEN_2022_PROGRAM_ID,
                     accounts: approve_accounts,
                     data: approve_data,
                 };
@@ -819,10 +1257,10 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 let tx = Transaction::new_signed_with_payer(
                     &[instruction],
                     Some(&payer.pubkey()),
-                    &[&payer, &authority],
+                    &[&payer, authority],
                     blockhash,
                 );
-                let _ = svm.send_transaction(tx);
+                svm.send_transaction(tx).expect("Approve before Revoke failed");
             }
 
             let accounts = vec![
@@ -831,7 +1269,8 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![4u8]; // discriminator for Revoke
-            (accounts, data, true)
+            let expected = vec![Expect::Delegate(source_token, None)];
+            (accounts, data, true, expected)
         }
         TokenOp::FreezeAccount => {
             let accounts = vec![
@@ -841,7 +1280,8 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![6u8]; // discriminator for FreezeAccount
-            (accounts, data, true)
+            let expected = vec![Expect::State(source_token, AccountState::Frozen)];
+            (accounts, data, true, expected)
         }
         TokenOp::ThawAccount => {
             // First freeze the account
@@ -863,10 +1303,10 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 let tx = Transaction::new_signed_with_payer(
                     &[instruction],
                     Some(&payer.pubkey()),
-                    &[&payer, &authority],
+                    &[&payer, authority],
                     blockhash,
                 );
-                let _ = svm.send_transaction(tx);
+                svm.send_transaction(tx).expect("FreezeAccount before ThawAccount failed");
             }
 
             let accounts = vec![
@@ -876,94 +1316,101 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![7u8]; // discriminator for ThawAccount
-            (accounts, data, true)
+            let expected = vec![Expect::State(source_token, AccountState::Initialized)];
+            (accounts, data, true, expected)
         }
         TokenOp::CloseAccount => {
             // Create a fresh token account with zero balance for closing
             let close_token = Pubkey::new_unique();
-            let close_data = create_token_account_data(&mint, &authority.pubkey(), 0);
+            let close_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build_token_2022(&extensions.account_extensions);
             svm.set_account(
                 close_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: close_data,
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(TOKEN_2022_PROGRAM_ID, close_data),
             )
             .unwrap();
 
+            let close_dest = if variant == OpVariant::CloseToSelf {
+                close_token
+            } else {
+                authority.pubkey()
+            };
             let accounts = vec![
                 AccountMeta::new(close_token, false),
-                AccountMeta::new(authority.pubkey(), false),
+                AccountMeta::new(close_dest, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![5u8]; // discriminator for CloseAccount
-            (accounts, data, true)
+            // Closing into itself is rejected, leaving the account open
+            let expected = if close_dest == close_token {
+                vec![Expect::Amount(close_token, 0)]
+            } else {
+                vec![Expect::Closed(close_token)]
+            };
+            (accounts, data, true, expected)
         }
         TokenOp::InitializeMint => {
             // Create uninitialized mint account
             let new_mint = Pubkey::new_unique();
             svm.set_account(
                 new_mint,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 82], // Mint size
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(TOKEN_2022_PROGRAM_ID, vec![0u8; Mint::LEN]),
             )
             .unwrap();
 
-            let accounts = vec![
+            let mut accounts = vec![
                 AccountMeta::new(new_mint, false),
                 AccountMeta::new_readonly(solana_sdk::sysvar::rent::ID, false),
                 AccountMeta::new_readonly(authority.pubkey(), false),
-                AccountMeta::new_readonly(authority.pubkey(), false), // freeze authority
-                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
-            let data = vec![9u8, 9, 1]; // discriminator, decimals, has_freeze_authority
-            (accounts, data, false) // no authority signer needed
+            if has_freeze_authority != 0 {
+                accounts.push(AccountMeta::new_readonly(authority.pubkey(), false)); // freeze authority
+            }
+            accounts.push(AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false));
+            let data = vec![9u8, 9, has_freeze_authority]; // discriminator, decimals, has_freeze_authority
+            let freeze_authority = (has_freeze_authority != 0).then_some(authority.pubkey());
+            let expected = vec![
+                Expect::MintAuthority(new_mint, Some(authority.pubkey())),
+                Expect::FreezeAuthority(new_mint, freeze_authority),
+                Expect::Supply(new_mint, 0),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeMint2 => {
             // Create uninitialized mint account
             let new_mint = Pubkey::new_unique();
             svm.set_account(
                 new_mint,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 82], // Mint size
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(TOKEN_2022_PROGRAM_ID, vec![0u8; Mint::LEN]),
             )
             .unwrap();
 
-            let accounts = vec![
+            let mut accounts = vec![
                 AccountMeta::new(new_mint, false),
                 AccountMeta::new_readonly(authority.pubkey(), false),
-                AccountMeta::new_readonly(authority.pubkey(), false), // freeze authority
-                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
-            let data = vec![10u8, 9, 1]; // discriminator, decimals, has_freeze_authority
-            (accounts, data, false) // no authority signer needed
+            if has_freeze_authority != 0 {
+                accounts.push(AccountMeta::new_readonly(authority.pubkey(), false)); // freeze authority
+            }
+            accounts.push(AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false));
+            let data = vec![10u8, 9, has_freeze_authority]; // discriminator, decimals, has_freeze_authority
+            let freeze_authority = (has_freeze_authority != 0).then_some(authority.pubkey());
+            let expected = vec![
+                Expect::MintAuthority(new_mint, Some(authority.pubkey())),
+                Expect::FreezeAuthority(new_mint, freeze_authority),
+                Expect::Supply(new_mint, 0),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(
+                    TOKEN_2022_PROGRAM_ID,
+                    vec![0u8; extensions.initialized_account_len()],
+                ),
             )
             .unwrap();
 
@@ -975,20 +1422,22 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![11u8]; // discriminator for InitializeAccount
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount2 => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(
+                    TOKEN_2022_PROGRAM_ID,
+                    vec![0u8; extensions.initialized_account_len()],
+                ),
             )
             .unwrap();
 
@@ -1000,20 +1449,22 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![12u8]; // discriminator for InitializeAccount2
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
         TokenOp::InitializeAccount3 => {
             // Create uninitialized token account
             let new_token = Pubkey::new_unique();
             svm.set_account(
                 new_token,
-                Account {
-                    lamports: 1_000_000_000,
-                    data: vec![0u8; 165], // Token account size
-                    owner: TOKEN_2022_PROGRAM_ID,
-                    executable: false,
-                    rent_epoch: 0,
-                },
+                fixture_account(
+                    TOKEN_2022_PROGRAM_ID,
+                    vec![0u8; extensions.initialized_account_len()],
+                ),
             )
             .unwrap();
 
@@ -1024,18 +1475,43 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
             let data = vec![13u8]; // discriminator for InitializeAccount3
-            (accounts, data, false) // no authority signer needed
+            let expected = vec![
+                Expect::Owner(new_token, authority.pubkey()),
+                Expect::Amount(new_token, 0),
+                Expect::State(new_token, AccountState::Initialized),
+            ];
+            (accounts, data, false, expected) // no authority signer needed
         }
-        TokenOp::SetAuthority => {
+        TokenOp::SetAuthority(authority_type) => {
+            // AccountOwner and CloseAccount live on the token account, everything else on the mint
+            let target = match authority_type {
+                2 | 3 => source_token,
+                _ => mint,
+            };
             let new_authority = Pubkey::new_unique();
             let accounts = vec![
-                AccountMeta::new(mint, false),
+                AccountMeta::new(target, false),
                 AccountMeta::new_readonly(authority.pubkey(), true),
                 AccountMeta::new_readonly(new_authority, false), // new authority
                 AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
             ];
-            let data = vec![14u8, 0, 1]; // discriminator, authority_type (MintTokens=0), has_new_authority
-            (accounts, data, true) // authority signer needed
+            let data = vec![14u8, authority_type, has_new_authority]; // discriminator, authority_type, has_new_authority
+            // A rejected SetAuthority leaves the current authority in place
+            let expected_authority = if expects_failure {
+                Some(authority.pubkey())
+            } else {
+                (has_new_authority != 0).then_some(new_authority)
+            };
+            let expected = match (authority_type, expected_authority) {
+                (0 | INVALID_AUTHORITY_TYPE, _) => {
+                    vec![Expect::MintAuthority(mint, expected_authority)]
+                }
+                (1, _) => vec![Expect::FreezeAuthority(mint, expected_authority)],
+                (2, Some(owner)) => vec![Expect::Owner(source_token, owner)],
+                (3, _) => vec![Expect::CloseAuthority(source_token, expected_authority)],
+                _ => vec![Expect::ExtensionAuthority(mint, authority_type, expected_authority)],
+            };
+            (accounts, data, true, expected) // authority signer needed
         }
     };
 
@@ -1050,7 +1526,7 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
         Transaction::new_signed_with_payer(
             &[instruction],
             Some(&payer.pubkey()),
-            &[&payer, &authority],
+            &[&payer, authority],
             blockhash,
         )
     } else {
@@ -1062,70 +1538,18 @@ fn run_token_2022_benchmark(token_ops_bytes: &[u8], op: TokenOp) -> u64 {
         )
     };
 
-    match svm.send_transaction(tx) {
-        Ok(tx_result) => tx_result.compute_units_consumed,
-        Err(e) => {
-            eprintln!("Transaction failed for {:?} (Token-2022): {:?}", op as u8, e);
-            e.meta.compute_units_consumed
-        }
-    }
-}
-
-/// Creates mint account data in SPL Token format
-fn create_mint_data(mint_authority: &Pubkey, freeze_authority: Option<&Pubkey>, decimals: u8, supply: u64) -> Vec<u8> {
-    let mut data = vec![0u8; 82]; // SPL Token Mint size
-
-    // mint_authority (COption<Pubkey>)
-    data[0..4].copy_from_slice(&1u32.to_le_bytes()); // Some
-    data[4..36].copy_from_slice(mint_authority.as_ref());
-
-    // supply (u64)
-    data[36..44].copy_from_slice(&supply.to_le_bytes());
-
-    // decimals (u8)
-    data[44] = decimals;
-
-    // is_initialized (bool)
-    data[45] = 1;
+    let cu = match svm.send_transaction(tx) {
+        Ok(tx_result) if !expects_failure => tx_result.compute_units_consumed,
+        Ok(_) => panic!("Transaction unexpectedly succeeded for {:?} ({:?}, Token-2022)", op, variant),
+        Err(e) if expects_failure => e.meta.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {:?} ({:?}, Token-2022): {:?}", op, variant, e),
+    };
 
-    // freeze_authority (COption<Pubkey>)
-    if let Some(auth) = freeze_authority {
-        data[46..50].copy_from_slice(&1u32.to_le_bytes()); // Some
-        data[50..82].copy_from_slice(auth.as_ref());
-    } else {
-        data[46..50].copy_from_slice(&0u32.to_le_bytes()); // None
+    // The expectations describe the op as if no extension rejected it
+    if !extensions.rejects(op) {
+        if let Err(e) = verify_post_state(&svm, Backend::Token2022, &expected) {
+            panic!("Post-state mismatch for {:?} ({:?}, Token-2022): {}", op, variant, e);
+        }
     }
-
-    data
-}
-
-/// Creates token account data in SPL Token format
-fn create_token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
-    let mut data = vec![0u8; 165]; // SPL Token Account size
-
-    // mint (Pubkey)
-    data[0..32].copy_from_slice(mint.as_ref());
-
-    // owner (Pubkey)
-    data[32..64].copy_from_slice(owner.as_ref());
-
-    // amount (u64)
-    data[64..72].copy_from_slice(&amount.to_le_bytes());
-
-    // delegate (COption<Pubkey>) - None
-    data[72..76].copy_from_slice(&0u32.to_le_bytes());
-
-    // state (AccountState) - 1 = Initialized
-    data[108] = 1;
-
-    // is_native (COption<u64>) - None
-    data[109..113].copy_from_slice(&0u32.to_le_bytes());
-
-    // delegated_amount (u64)
-    data[121..129].copy_from_slice(&0u64.to_le_bytes());
-
-    // close_authority (COption<Pubkey>) - None
-    data[129..133].copy_from_slice(&0u32.to_le_bytes());
-
-    data
+    cu
 }
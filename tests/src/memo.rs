@@ -0,0 +1,303 @@
+//! Memo benchmarks: a memo CPI through token-ops with a growing memo, and
+//! Token-2022 transfers into a token account that requires memos, each
+//! preceded by a memo CPI from token-ops-2022.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token_2022::error::TokenError;
+
+use crate::{
+    fixture_account,
+    fixtures::{AccountExtension, MintBuilder, TokenAccountBuilder},
+    is_token_error, load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// Memo lengths of the memo CPI benchmark, in bytes
+const MEMO_LENGTHS: &[usize] = &[0, 1, 32, 64, 128, 256, 500];
+
+/// Memo preceding the memo-required transfers
+const TRANSFER_MEMO: &[u8] = b"benchmark transfer memo";
+
+/// Amount moved by every transfer
+const AMOUNT: u64 = 1000;
+
+/// Balance of the fixture source token account
+const SOURCE_BALANCE: u64 = 1_000_000_000;
+
+/// Installs the bundled SPL Memo program, so the benchmarks do not depend on
+/// the memo version LiteSVM loads by default
+fn add_memo_program(svm: &mut LiteSVM) {
+    svm.add_program(spl_memo::ID, include_bytes!("spl_memo.so"));
+}
+
+/// Whether the memo program logged `memo`
+fn logged_memo(logs: &[String], memo: &[u8]) -> bool {
+    let memo = std::str::from_utf8(memo).expect("memos are UTF-8");
+    let expected = format!("Memo (len {}): {:?}", memo.len(), memo);
+    logs.iter().any(|log| log.ends_with(&expected))
+}
+
+/// Runs the token-ops Memo op with a `memo_len` byte memo and no signers
+fn run_memo_benchmark(token_ops_bytes: &[u8], memo_len: usize) -> u64 {
+    let backend = Backend::Token;
+    let mut svm = LiteSVM::new();
+    add_memo_program(&mut svm);
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let memo = vec![b'm'; memo_len];
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts: vec![AccountMeta::new_readonly(spl_memo::ID, false)],
+        data: [&[45u8][..], &memo].concat(),
+    };
+    match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => {
+            if !logged_memo(&tx_result.logs, &memo) {
+                panic!("Memo ({} bytes) was not logged", memo_len);
+            }
+            tx_result.compute_units_consumed
+        }
+        Err(e) => panic!("Transaction failed for Memo ({} bytes): {:?}", memo_len, e),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum MemoTransferOp {
+    EnableRequiredMemoTransfers,
+    /// TransferChecked without a memo
+    TransferChecked {
+        memo_required: bool,
+    },
+    /// TransferChecked preceded by a memo CPI
+    TransferCheckedWithMemo {
+        memo_required: bool,
+    },
+}
+
+impl MemoTransferOp {
+    /// Whether the destination requires memos before the op runs
+    fn memo_required(self) -> bool {
+        match self {
+            MemoTransferOp::EnableRequiredMemoTransfers => false,
+            MemoTransferOp::TransferChecked { memo_required }
+            | MemoTransferOp::TransferCheckedWithMemo { memo_required } => memo_required,
+        }
+    }
+
+    /// Token-2022 rejects a transfer without a memo into an account
+    /// requiring memos
+    fn expects_failure(self) -> bool {
+        matches!(
+            self,
+            MemoTransferOp::TransferChecked {
+                memo_required: true
+            }
+        )
+    }
+}
+
+/// Runs `op` against a destination token account carrying the MemoTransfer
+/// extension, with `owner` owning both token accounts
+fn run_memo_transfer_benchmark(token_ops_bytes: &[u8], op: MemoTransferOp) -> u64 {
+    let backend = Backend::Token2022;
+    let mut svm = LiteSVM::new();
+    add_memo_program(&mut svm);
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let owner = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&owner.pubkey())
+        .supply(SOURCE_BALANCE)
+        .build_token_2022(&[]);
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let source_data = TokenAccountBuilder::new(&mint, &owner.pubkey())
+        .amount(SOURCE_BALANCE)
+        .build_token_2022(&[]);
+    svm.set_account(
+        source_token,
+        fixture_account(backend.token_program(), source_data),
+    )
+    .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &owner.pubkey()).build_token_2022(&[
+        AccountExtension::MemoTransfer {
+            require_incoming_transfer_memos: op.memo_required(),
+        },
+    ]);
+    svm.set_account(
+        dest_token,
+        fixture_account(backend.token_program(), dest_data),
+    )
+    .unwrap();
+
+    let transfer_accounts = vec![
+        AccountMeta::new(source_token, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new(dest_token, false),
+        AccountMeta::new_readonly(owner.pubkey(), true),
+        AccountMeta::new_readonly(backend.token_program(), false),
+    ];
+    let transferred = [
+        Expect::Amount(source_token, SOURCE_BALANCE - AMOUNT),
+        Expect::Amount(dest_token, AMOUNT),
+    ];
+
+    let (accounts, data, expected) = match op {
+        MemoTransferOp::EnableRequiredMemoTransfers => (
+            vec![
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+                AccountMeta::new_readonly(backend.token_program(), false),
+            ],
+            vec![46u8],
+            vec![Expect::MemoRequired(dest_token, true)],
+        ),
+        MemoTransferOp::TransferChecked { .. } => (
+            transfer_accounts,
+            [&[8u8][..], &AMOUNT.to_le_bytes(), &[9]].concat(),
+            transferred.to_vec(),
+        ),
+        MemoTransferOp::TransferCheckedWithMemo { .. } => {
+            let mut accounts = transfer_accounts;
+            accounts.push(AccountMeta::new_readonly(spl_memo::ID, false));
+            (
+                accounts,
+                [&[47u8][..], &AMOUNT.to_le_bytes(), &[9], TRANSFER_MEMO].concat(),
+                transferred.to_vec(),
+            )
+        }
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[&owner]) {
+        Ok(tx_result) => {
+            if op.expects_failure() {
+                panic!("Transaction unexpectedly succeeded for {:?}", op);
+            }
+            if let MemoTransferOp::TransferCheckedWithMemo { .. } = op {
+                if !logged_memo(&tx_result.logs, TRANSFER_MEMO) {
+                    panic!("Memo was not logged for {:?}", op);
+                }
+            }
+            tx_result.compute_units_consumed
+        }
+        Err(e) => {
+            if !op.expects_failure() {
+                panic!("Transaction failed for {:?}: {:?}", op, e);
+            }
+            if !is_token_error(&e, TokenError::NoMemo) {
+                panic!("{:?} failed without NoMemo: {:?}", op, e.err);
+            }
+            // A rejected transfer leaves both balances in place
+            let expected = [
+                Expect::Amount(source_token, SOURCE_BALANCE),
+                Expect::Amount(dest_token, 0),
+            ];
+            if let Err(e) = verify_post_state(&svm, backend, &expected) {
+                panic!("Post-state mismatch for {:?}: {}", op, e);
+            }
+            return e.meta.compute_units_consumed;
+        }
+    };
+
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!("Post-state mismatch for {:?}: {}", op, e);
+    }
+    cu
+}
+
+pub fn benchmark_memo() {
+    let Some(token_ops_bytes) = load_program("token-ops") else {
+        return;
+    };
+
+    println!(
+        "{:>12} {:>12} {:>12}",
+        "Memo bytes", "CU Consumed", "vs empty"
+    );
+    println!("{}", "-".repeat(38));
+
+    let empty_cu = run_memo_benchmark(&token_ops_bytes, 0);
+    for &memo_len in MEMO_LENGTHS {
+        let cu = if memo_len == 0 {
+            empty_cu
+        } else {
+            run_memo_benchmark(&token_ops_bytes, memo_len)
+        };
+        println!(
+            "{:>12} {:>12} {:>+12}",
+            memo_len,
+            cu,
+            cu as i64 - empty_cu as i64
+        );
+    }
+}
+
+pub fn benchmark_memo_transfer() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!("{:<45} {:>12}", "Operation", "CU Consumed");
+    println!("{}", "-".repeat(58));
+
+    let cu = run_memo_transfer_benchmark(
+        &token_ops_2022_bytes,
+        MemoTransferOp::EnableRequiredMemoTransfers,
+    );
+    println!("{:<45} {:>12}", "EnableRequiredMemoTransfers", cu);
+
+    // The memo cost is the gap between the transfers with and without one,
+    // the memo check's cost the gap between required and not
+    for (name, op) in [
+        (
+            "TransferChecked",
+            MemoTransferOp::TransferChecked {
+                memo_required: false,
+            },
+        ),
+        (
+            "TransferChecked, memo required (NoMemo)",
+            MemoTransferOp::TransferChecked {
+                memo_required: true,
+            },
+        ),
+        (
+            "memo + TransferChecked",
+            MemoTransferOp::TransferCheckedWithMemo {
+                memo_required: false,
+            },
+        ),
+        (
+            "memo + TransferChecked, memo required",
+            MemoTransferOp::TransferCheckedWithMemo {
+                memo_required: true,
+            },
+        ),
+    ] {
+        let cu = run_memo_transfer_benchmark(&token_ops_2022_bytes, op);
+        println!("{:<45} {:>12}", name, cu);
+    }
+}
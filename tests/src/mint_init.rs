@@ -0,0 +1,211 @@
+//! Mint creation with extensions: each Token-2022 mint extension initializer
+//! followed by InitializeMint2, through token-ops-2022, on an uninitialized
+//! mint sized for exactly that extension, next to InitializeMint2 alone.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token::state::{AccountState, Mint};
+
+use crate::{
+    fixture_account,
+    fixtures::{token_2022_mint_len, MintExtension},
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// Rate the interest-bearing mint is initialized with, in basis points
+const INTEREST_RATE: i16 = 500;
+
+/// State the default-account-state mint gives new token accounts
+const DEFAULT_ACCOUNT_STATE: AccountState = AccountState::Frozen;
+
+#[derive(Clone, Copy, Debug)]
+enum MintInit {
+    MintCloseAuthority,
+    NonTransferableMint,
+    PermanentDelegate,
+    DefaultAccountState,
+    InterestBearingMint,
+    MetadataPointer,
+    GroupPointer,
+}
+
+impl MintInit {
+    const ALL: [MintInit; 7] = [
+        MintInit::MintCloseAuthority,
+        MintInit::NonTransferableMint,
+        MintInit::PermanentDelegate,
+        MintInit::DefaultAccountState,
+        MintInit::InterestBearingMint,
+        MintInit::MetadataPointer,
+        MintInit::GroupPointer,
+    ];
+
+    /// token-ops-2022 instruction data, with 9 decimals
+    fn data(self) -> Vec<u8> {
+        match self {
+            MintInit::MintCloseAuthority => vec![38, 9],
+            MintInit::NonTransferableMint => vec![39, 9],
+            MintInit::PermanentDelegate => vec![40, 9],
+            MintInit::DefaultAccountState => vec![41, 9, DEFAULT_ACCOUNT_STATE as u8],
+            MintInit::InterestBearingMint => {
+                [&[42u8, 9][..], &INTEREST_RATE.to_le_bytes()].concat()
+            }
+            MintInit::MetadataPointer => vec![43, 9],
+            MintInit::GroupPointer => vec![44, 9],
+        }
+    }
+
+    /// The extension the initializer leaves on `mint`, as token-ops-2022
+    /// configures it
+    fn extension(self, mint: &Pubkey, authority: &Pubkey) -> MintExtension {
+        let (mint, authority) = (*mint, *authority);
+        match self {
+            MintInit::MintCloseAuthority => MintExtension::MintCloseAuthority(authority),
+            MintInit::NonTransferableMint => MintExtension::NonTransferable,
+            MintInit::PermanentDelegate => MintExtension::PermanentDelegate(authority),
+            MintInit::DefaultAccountState => {
+                MintExtension::DefaultAccountState(DEFAULT_ACCOUNT_STATE)
+            }
+            MintInit::InterestBearingMint => MintExtension::InterestBearingConfig {
+                authority,
+                rate: INTEREST_RATE,
+            },
+            MintInit::MetadataPointer => MintExtension::MetadataPointer {
+                authority,
+                metadata_address: mint,
+            },
+            MintInit::GroupPointer => MintExtension::GroupPointer {
+                authority,
+                group_address: mint,
+            },
+        }
+    }
+
+    /// Extension state to check beyond the extension being present
+    fn expected(self, mint: &Pubkey, authority: &Pubkey) -> Vec<Expect> {
+        let (mint, authority) = (*mint, Some(*authority));
+        match self {
+            MintInit::MintCloseAuthority => vec![Expect::ExtensionAuthority(mint, 6, authority)],
+            MintInit::NonTransferableMint => vec![],
+            MintInit::PermanentDelegate => vec![Expect::ExtensionAuthority(mint, 8, authority)],
+            MintInit::DefaultAccountState => {
+                vec![Expect::DefaultAccountState(mint, DEFAULT_ACCOUNT_STATE)]
+            }
+            MintInit::InterestBearingMint => vec![
+                Expect::ExtensionAuthority(mint, 7, authority),
+                Expect::InterestRate(mint, INTEREST_RATE),
+            ],
+            MintInit::MetadataPointer => vec![Expect::ExtensionAuthority(mint, 12, authority)],
+            MintInit::GroupPointer => vec![Expect::ExtensionAuthority(mint, 13, authority)],
+        }
+    }
+}
+
+/// Creates a mint through token-ops-2022 with the `init` extension, or with
+/// InitializeMint2 alone when `init` is `None`. Returns the mint data length
+/// and the CU consumed.
+fn run_mint_init_benchmark(token_ops_bytes: &[u8], init: Option<MintInit>) -> (usize, u64) {
+    let backend = Backend::Token2022;
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let authority = Pubkey::new_unique();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    // Token-2022 requires the mint to be exactly the size of its extensions
+    let mint = Pubkey::new_unique();
+    let mint_len = match init {
+        Some(init) => token_2022_mint_len(&[init.extension(&mint, &authority)]),
+        None => Mint::LEN,
+    };
+    svm.set_account(
+        mint,
+        fixture_account(backend.token_program(), vec![0u8; mint_len]),
+    )
+    .unwrap();
+
+    let mut expected = vec![
+        Expect::MintAuthority(mint, Some(authority)),
+        Expect::FreezeAuthority(mint, Some(authority)),
+        Expect::Supply(mint, 0),
+    ];
+    let (accounts, data) = match init {
+        Some(init) => {
+            let extension_type = init.extension(&mint, &authority).extension_type();
+            expected.push(Expect::HasMintExtension(mint, extension_type));
+            expected.extend(init.expected(&mint, &authority));
+            (
+                vec![
+                    AccountMeta::new(mint, false),
+                    AccountMeta::new_readonly(authority, false),
+                    AccountMeta::new_readonly(backend.token_program(), false),
+                ],
+                init.data(),
+            )
+        }
+        // InitializeMint2 with a freeze authority, like the initializers
+        None => (
+            vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority, false),
+                AccountMeta::new_readonly(authority, false),
+                AccountMeta::new_readonly(backend.token_program(), false),
+            ],
+            vec![10, 9, 1],
+        ),
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {:?}: {:?}", init, e),
+    };
+
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!("Post-state mismatch for {:?}: {}", init, e);
+    }
+    (mint_len, cu)
+}
+
+pub fn benchmark_mint_init() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!(
+        "{:<45} {:>10} {:>12} {:>12}",
+        "Initializer + InitializeMint2", "Mint bytes", "CU Consumed", "Surcharge"
+    );
+    println!("{}", "-".repeat(82));
+
+    let (plain_len, plain_cu) = run_mint_init_benchmark(&token_ops_2022_bytes, None);
+    println!(
+        "{:<45} {:>10} {:>12} {:>12}",
+        "(InitializeMint2 only)", plain_len, plain_cu, "-"
+    );
+
+    for init in MintInit::ALL {
+        let (mint_len, cu) = run_mint_init_benchmark(&token_ops_2022_bytes, Some(init));
+        println!(
+            "{:<45} {:>10} {:>12} {:>+12}",
+            format!("Initialize{:?}", init),
+            mint_len,
+            cu,
+            cu as i64 - plain_cu as i64
+        );
+    }
+}
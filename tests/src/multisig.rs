@@ -0,0 +1,280 @@
+//! Multisig authority benchmarks: InitializeMultisig and InitializeMultisig2
+//! as ops, and token CPIs authorized by m-of-n multisigs through the
+//! token-ops Multisig operation (discriminator 23).
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+};
+use spl_token::{instruction::MAX_SIGNERS, state::Multisig};
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, MultisigBuilder, TokenAccountBuilder},
+    send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TOKEN_OPS_PROGRAM_ID,
+};
+
+/// Supply of the fixture mint, all of it held by the source token account
+const SUPPLY: u64 = 1_000_000_000;
+
+/// Amount moved, minted, burned or approved by every case
+const AMOUNT: u64 = 1000;
+
+const DECIMALS: u8 = 9;
+
+/// Operations the token-ops Multisig operation can run
+#[derive(Clone, Copy)]
+enum MultisigOp {
+    Transfer,
+    MintTo,
+    Burn,
+    Approve,
+    TransferChecked,
+}
+
+/// Signer keypairs plus an SVM with SPL Token and token-ops loaded
+struct MultisigFixture {
+    svm: LiteSVM,
+    payer: Keypair,
+    signers: Vec<Keypair>,
+}
+
+impl MultisigFixture {
+    fn new(token_ops_bytes: &[u8], signer_count: usize) -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program(spl_token::ID, include_bytes!("spl_token.so"));
+        svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        Self {
+            svm,
+            payer,
+            signers: (0..signer_count).map(|_| Keypair::new()).collect(),
+        }
+    }
+
+    fn signer_pubkeys(&self) -> Vec<Pubkey> {
+        self.signers.iter().map(Keypair::pubkey).collect()
+    }
+
+    /// Runs one token-ops instruction signed by the first `signer_count`
+    /// multisig signers, checks it left the `expected` state and returns the
+    /// CU it consumed
+    fn run(
+        &mut self,
+        label: &str,
+        accounts: Vec<AccountMeta>,
+        data: Vec<u8>,
+        signer_count: usize,
+        expected: &[Expect],
+    ) -> u64 {
+        let instruction = Instruction {
+            program_id: TOKEN_OPS_PROGRAM_ID,
+            accounts,
+            data,
+        };
+        let signers: Vec<&Keypair> = self.signers[..signer_count].iter().collect();
+        let cu = match send_instructions(&mut self.svm, &[instruction], &self.payer, &signers) {
+            Ok(tx_result) => tx_result.compute_units_consumed,
+            Err(e) => panic!("Transaction failed for {}: {:?}", label, e),
+        };
+        if let Err(e) = verify_post_state(&self.svm, Backend::Token, expected) {
+            panic!("Post-state mismatch for {}: {}", label, e);
+        }
+        cu
+    }
+
+    /// Benchmarks InitializeMultisig (`discriminator` 21) or InitializeMultisig2
+    /// (22) of an n-of-n multisig over every fixture signer
+    fn run_initialize(&mut self, discriminator: u8) -> u64 {
+        let multisig = Pubkey::new_unique();
+        self.svm
+            .set_account(
+                multisig,
+                fixture_account(spl_token::ID, vec![0u8; Multisig::LEN]),
+            )
+            .unwrap();
+
+        let mut accounts = vec![AccountMeta::new(multisig, false)];
+        if discriminator == 21 {
+            accounts.push(AccountMeta::new_readonly(sysvar::rent::ID, false));
+        }
+        accounts.push(AccountMeta::new_readonly(spl_token::ID, false));
+        accounts.extend(
+            self.signer_pubkeys()
+                .into_iter()
+                .map(|signer| AccountMeta::new_readonly(signer, false)),
+        );
+
+        let label = format!("InitializeMultisig (discriminator {})", discriminator);
+        let n = self.signers.len() as u8;
+        let data = vec![discriminator, n];
+        let expected = [Expect::Multisig(multisig, n, n)];
+        self.run(&label, accounts, data, 0, &expected)
+    }
+
+    /// Benchmarks `op` with an m-of-n multisig authority, signed by the first m signers
+    fn run_op(&mut self, op: MultisigOp, m: usize) -> u64 {
+        let multisig = Pubkey::new_unique();
+        let multisig_data = MultisigBuilder::new(m as u8, &self.signer_pubkeys()).build();
+        self.svm
+            .set_account(multisig, fixture_account(spl_token::ID, multisig_data))
+            .unwrap();
+
+        let mint = Pubkey::new_unique();
+        let mint_data = MintBuilder::new(&multisig)
+            .decimals(DECIMALS)
+            .supply(SUPPLY)
+            .build();
+        self.svm
+            .set_account(mint, fixture_account(spl_token::ID, mint_data))
+            .unwrap();
+
+        let source_token = Pubkey::new_unique();
+        let source_data = TokenAccountBuilder::new(&mint, &multisig)
+            .amount(SUPPLY)
+            .build();
+        self.svm
+            .set_account(source_token, fixture_account(spl_token::ID, source_data))
+            .unwrap();
+
+        let dest_token = Pubkey::new_unique();
+        let dest_data = TokenAccountBuilder::new(&mint, &multisig).build();
+        self.svm
+            .set_account(dest_token, fixture_account(spl_token::ID, dest_data))
+            .unwrap();
+
+        let delegate = Pubkey::new_unique();
+        let transferred = vec![
+            Expect::Amount(source_token, SUPPLY - AMOUNT),
+            Expect::Amount(dest_token, AMOUNT),
+        ];
+
+        // Same accounts as the plain op, with the multisig as the authority
+        let (operation, mut accounts, expected) = match op {
+            MultisigOp::Transfer => (
+                0u8,
+                vec![
+                    AccountMeta::new(source_token, false),
+                    AccountMeta::new(dest_token, false),
+                    AccountMeta::new_readonly(multisig, false),
+                ],
+                transferred,
+            ),
+            MultisigOp::MintTo => (
+                1u8,
+                vec![
+                    AccountMeta::new(mint, false),
+                    AccountMeta::new(dest_token, false),
+                    AccountMeta::new_readonly(multisig, false),
+                ],
+                vec![
+                    Expect::Supply(mint, SUPPLY + AMOUNT),
+                    Expect::Amount(dest_token, AMOUNT),
+                ],
+            ),
+            MultisigOp::Burn => (
+                2u8,
+                vec![
+                    AccountMeta::new(source_token, false),
+                    AccountMeta::new(mint, false),
+                    AccountMeta::new_readonly(multisig, false),
+                ],
+                vec![
+                    Expect::Supply(mint, SUPPLY - AMOUNT),
+                    Expect::Amount(source_token, SUPPLY - AMOUNT),
+                ],
+            ),
+            MultisigOp::Approve => (
+                3u8,
+                vec![
+                    AccountMeta::new(source_token, false),
+                    AccountMeta::new_readonly(delegate, false),
+                    AccountMeta::new_readonly(multisig, false),
+                ],
+                vec![Expect::Delegate(source_token, Some((delegate, AMOUNT)))],
+            ),
+            MultisigOp::TransferChecked => (
+                8u8,
+                vec![
+                    AccountMeta::new(source_token, false),
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new(dest_token, false),
+                    AccountMeta::new_readonly(multisig, false),
+                ],
+                transferred,
+            ),
+        };
+        accounts.push(AccountMeta::new_readonly(spl_token::ID, false));
+        accounts.extend(
+            self.signers[..m]
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(signer.pubkey(), true)),
+        );
+
+        let mut data = vec![23u8, operation]; // discriminator for Multisig
+        data.extend_from_slice(&AMOUNT.to_le_bytes());
+        if let MultisigOp::TransferChecked = op {
+            data.push(DECIMALS);
+        }
+
+        let label = format!(
+            "Multisig op {} ({}-of-{})",
+            operation,
+            m,
+            self.signers.len()
+        );
+        self.run(&label, accounts, data, m, &expected)
+    }
+}
+
+pub fn benchmark_multisig(token_ops_bytes: &[u8]) {
+    // Every op column is n-of-n except Transfer 1/n
+    println!(
+        "{:<8} {:>13} {:>13} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "Signers",
+        "InitMultisig",
+        "InitMultisig2",
+        "Transfer",
+        "Transfer 1/n",
+        "TransferChk",
+        "MintTo",
+        "Burn",
+        "Approve"
+    );
+    println!("{}", "-".repeat(114));
+
+    for n in 1..=MAX_SIGNERS {
+        let mut fixture = MultisigFixture::new(token_ops_bytes, n);
+        let initialize_cu = fixture.run_initialize(21);
+        let initialize2_cu = fixture.run_initialize(22);
+        let transfer_all_cu = fixture.run_op(MultisigOp::Transfer, n);
+        let transfer_one_cu = fixture.run_op(MultisigOp::Transfer, 1);
+        let transfer_checked_cu = fixture.run_op(MultisigOp::TransferChecked, n);
+        let mint_to_cu = fixture.run_op(MultisigOp::MintTo, n);
+        let burn_cu = fixture.run_op(MultisigOp::Burn, n);
+        let approve_cu = fixture.run_op(MultisigOp::Approve, n);
+
+        println!(
+            "{:<8} {:>13} {:>13} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            n,
+            initialize_cu,
+            initialize2_cu,
+            transfer_all_cu,
+            transfer_one_cu,
+            transfer_checked_cu,
+            mint_to_cu,
+            burn_cu,
+            approve_cu
+        );
+    }
+}
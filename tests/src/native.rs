@@ -0,0 +1,235 @@
+//! Wrapped SOL benchmarks: SyncNative, Transfer and CloseAccount on native
+//! token accounts, plus Token-2022's CreateNativeMint and
+//! WithdrawExcessLamports, through both token-ops programs.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    fixture_account,
+    fixtures::TokenAccountBuilder,
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// Token-2022 native mint (9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP)
+const TOKEN_2022_NATIVE_MINT: Pubkey =
+    Pubkey::from_str_const("9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP");
+
+/// Wrapped SOL held by every native fixture account
+const WRAPPED_AMOUNT: u64 = 1_000_000_000;
+
+/// Lamports sent to a native account before SyncNative picks them up
+const DEPOSIT: u64 = 500_000_000;
+
+/// Wrapped SOL moved by Transfer
+const TRANSFER_AMOUNT: u64 = 1000;
+
+#[derive(Clone, Copy, Debug)]
+enum NativeCase {
+    SyncNative,
+    Transfer,
+    CloseAccount,
+    CreateNativeMint,
+    WithdrawExcessLamports,
+}
+
+impl NativeCase {
+    /// Whether only Token-2022 implements the instruction
+    fn token_2022_only(self) -> bool {
+        matches!(
+            self,
+            NativeCase::CreateNativeMint | NativeCase::WithdrawExcessLamports
+        )
+    }
+}
+
+/// Native mint of `backend`'s token program
+fn native_mint(backend: Backend) -> Pubkey {
+    match backend {
+        Backend::Token => spl_token::native_mint::ID,
+        Backend::Token2022 => TOKEN_2022_NATIVE_MINT,
+    }
+}
+
+/// Creates a native token account owned by `owner` holding `amount` wrapped
+/// SOL on top of its rent-exempt reserve
+fn create_native_account(
+    svm: &mut LiteSVM,
+    backend: Backend,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let rent_reserve = svm.minimum_balance_for_rent_exemption(TokenAccount::LEN);
+    let native_token = Pubkey::new_unique();
+    let data = TokenAccountBuilder::new(&native_mint(backend), owner)
+        .amount(amount)
+        .native(rent_reserve)
+        .build();
+    svm.set_account(
+        native_token,
+        Account {
+            lamports: rent_reserve + amount,
+            data,
+            owner: backend.token_program(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+    native_token
+}
+
+fn run_native_benchmark(token_ops_bytes: &[u8], backend: Backend, case: NativeCase) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let owner = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let token_program_meta = AccountMeta::new_readonly(backend.token_program(), false);
+
+    let (accounts, data, needs_owner_signer, expected) = match case {
+        NativeCase::SyncNative => {
+            // Lamports deposited straight into the account, not yet reflected in its amount
+            let native_token =
+                create_native_account(&mut svm, backend, &owner.pubkey(), WRAPPED_AMOUNT);
+            let mut account = svm.get_account(&native_token).unwrap();
+            account.lamports += DEPOSIT;
+            svm.set_account(native_token, account).unwrap();
+
+            let accounts = vec![AccountMeta::new(native_token, false), token_program_meta];
+            let expected = vec![Expect::Amount(native_token, WRAPPED_AMOUNT + DEPOSIT)];
+            (accounts, vec![25u8], false, expected) // discriminator for SyncNative
+        }
+        NativeCase::Transfer => {
+            let source = create_native_account(&mut svm, backend, &owner.pubkey(), WRAPPED_AMOUNT);
+            let destination = create_native_account(&mut svm, backend, &owner.pubkey(), 0);
+
+            let accounts = vec![
+                AccountMeta::new(source, false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+                token_program_meta,
+            ];
+            let mut data = vec![0u8]; // discriminator for Transfer
+            data.extend_from_slice(&TRANSFER_AMOUNT.to_le_bytes());
+            let expected = vec![
+                Expect::Amount(source, WRAPPED_AMOUNT - TRANSFER_AMOUNT),
+                Expect::Amount(destination, TRANSFER_AMOUNT),
+            ];
+            (accounts, data, true, expected)
+        }
+        NativeCase::CloseAccount => {
+            // Native accounts can be closed with a balance; all lamports go to the destination
+            let native_token =
+                create_native_account(&mut svm, backend, &owner.pubkey(), WRAPPED_AMOUNT);
+
+            let accounts = vec![
+                AccountMeta::new(native_token, false),
+                AccountMeta::new(owner.pubkey(), false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+                token_program_meta,
+            ];
+            let expected = vec![Expect::Closed(native_token)];
+            (accounts, vec![5u8], true, expected) // discriminator for CloseAccount
+        }
+        NativeCase::CreateNativeMint => {
+            let accounts = vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(native_mint(backend), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                token_program_meta,
+            ];
+            let expected = vec![
+                Expect::Supply(native_mint(backend), 0),
+                Expect::MintAuthority(native_mint(backend), None),
+            ];
+            (accounts, vec![26u8], false, expected) // discriminator for CreateNativeMint
+        }
+        NativeCase::WithdrawExcessLamports => {
+            // Native accounts are rejected, so withdraw from a regular token
+            // account funded well above rent
+            let token_account = Pubkey::new_unique();
+            let data = TokenAccountBuilder::new(&Pubkey::new_unique(), &owner.pubkey()).build();
+            svm.set_account(
+                token_account,
+                fixture_account(backend.token_program(), data),
+            )
+            .unwrap();
+
+            let accounts = vec![
+                AccountMeta::new(token_account, false),
+                AccountMeta::new(owner.pubkey(), false),
+                AccountMeta::new_readonly(owner.pubkey(), true),
+                token_program_meta,
+            ];
+            // Everything above the rent-exempt reserve goes to the owner
+            let rent_reserve = svm.minimum_balance_for_rent_exemption(TokenAccount::LEN);
+            let expected = vec![Expect::Lamports(token_account, rent_reserve)];
+            (accounts, vec![27u8], true, expected) // discriminator for WithdrawExcessLamports
+        }
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let signers: &[&Keypair] = if needs_owner_signer { &[&owner] } else { &[] };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, signers) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for wrapped SOL {:?}: {:?}", case, e),
+    };
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!("Post-state mismatch for wrapped SOL {:?}: {}", case, e);
+    }
+    cu
+}
+
+pub fn benchmark_native() {
+    let (Some(token_ops_bytes), Some(token_ops_2022_bytes)) =
+        (load_program("token-ops"), load_program("token-ops-2022"))
+    else {
+        return;
+    };
+
+    println!(
+        "{:<32} {:>12} {:>12}",
+        "Operation", "SPL Token", "Token-2022"
+    );
+    println!("{}", "-".repeat(58));
+
+    for case in [
+        NativeCase::SyncNative,
+        NativeCase::Transfer,
+        NativeCase::CloseAccount,
+        NativeCase::CreateNativeMint,
+        NativeCase::WithdrawExcessLamports,
+    ] {
+        let token_cu = if case.token_2022_only() {
+            "-".to_string()
+        } else {
+            run_native_benchmark(&token_ops_bytes, Backend::Token, case).to_string()
+        };
+        let token_2022_cu = run_native_benchmark(&token_ops_2022_bytes, Backend::Token2022, case);
+        println!(
+            "{:<32} {:>12} {:>12}",
+            format!("{:?}", case),
+            token_cu,
+            token_2022_cu
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! PDA-signed token CPI benchmarks: authority-taking ops wrapped in the
+//! token-ops PdaSigned operation (discriminator 24), whose CPIs are signed
+//! with `invoke_signed` by a token-ops PDA owning the token accounts.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token::state::AccountState;
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, TokenAccountBuilder},
+    send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TokenOp, TOKEN_OPS_PROGRAM_ID,
+};
+
+/// Authority-taking ops benchmarked with a keypair and with a PDA authority
+const PDA_OPS: &[TokenOp] = &[
+    TokenOp::Transfer,
+    TokenOp::TransferChecked,
+    TokenOp::MintTo,
+    TokenOp::MintToChecked,
+    TokenOp::Burn,
+    TokenOp::BurnChecked,
+    TokenOp::Approve,
+    TokenOp::ApproveChecked,
+    TokenOp::Revoke,
+    TokenOp::FreezeAccount,
+    TokenOp::ThawAccount,
+    TokenOp::CloseAccount,
+    TokenOp::SetAuthority(0),
+];
+
+/// Seed counts (excluding the bump) benchmarked for PDA-signed Transfer
+const SEED_COUNTS: &[u8] = &[1, 2, 4, 8, 15];
+
+/// Seed lengths benchmarked for PDA-signed Transfer
+const SEED_LENS: &[u8] = &[1, 8, 16, 32];
+
+/// Amount moved, minted, burned or approved by every case
+const AMOUNT: u64 = 1000;
+
+/// Supply of the fixture mint, all of it held by the source account
+const SUPPLY: u64 = 1_000_000_000;
+
+/// Who authorizes the benchmarked op
+#[derive(Clone, Copy)]
+enum Authority {
+    /// A keypair signing the transaction
+    Keypair,
+    /// A token-ops PDA derived from `seed_count` seeds of `seed_len` bytes
+    Pda { seed_count: u8, seed_len: u8 },
+}
+
+/// Seeds token-ops derives for PdaSigned: seed `i` is `seed_len` bytes of value `i`
+fn pda_seeds(seed_count: u8, seed_len: u8) -> Vec<Vec<u8>> {
+    (0..seed_count)
+        .map(|index| vec![index; seed_len as usize])
+        .collect()
+}
+
+/// Runs `op` with its mint and token accounts under `authority` and returns
+/// the CU it consumed
+fn run_pda_benchmark(token_ops_bytes: &[u8], op: TokenOp, authority: Authority) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(spl_token::ID, include_bytes!("spl_token.so"));
+    svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+
+    let payer = Keypair::new();
+    let keypair = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    // The PdaSigned prefix, and the authority the fixtures are created under
+    let (authority_key, prefix) = match authority {
+        Authority::Keypair => (keypair.pubkey(), vec![]),
+        Authority::Pda {
+            seed_count,
+            seed_len,
+        } => {
+            let seeds = pda_seeds(seed_count, seed_len);
+            let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+            let (pda, bump) = Pubkey::find_program_address(&seed_refs, &TOKEN_OPS_PROGRAM_ID);
+            // discriminator for PdaSigned, seed_count, seed_len, bump
+            (pda, vec![24u8, seed_count, seed_len, bump])
+        }
+    };
+    let is_signer = matches!(authority, Authority::Keypair);
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&authority_key)
+        .freeze_authority(&authority_key)
+        .supply(SUPPLY)
+        .build();
+    svm.set_account(mint, fixture_account(spl_token::ID, mint_data))
+        .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let delegate = Pubkey::new_unique();
+    let source = TokenAccountBuilder::new(&mint, &authority_key).amount(SUPPLY);
+    let source_data = match op {
+        // A delegate, so there is something to revoke
+        TokenOp::Revoke => source.delegate(&delegate, AMOUNT),
+        TokenOp::ThawAccount => source.state(AccountState::Frozen),
+        _ => source,
+    }
+    .build();
+    svm.set_account(source_token, fixture_account(spl_token::ID, source_data))
+        .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &authority_key).build();
+    svm.set_account(dest_token, fixture_account(spl_token::ID, dest_data))
+        .unwrap();
+
+    let authority_meta = AccountMeta::new_readonly(authority_key, is_signer);
+    let token_program_meta = AccountMeta::new_readonly(spl_token::ID, false);
+    let amount = AMOUNT.to_le_bytes();
+    let new_authority = Pubkey::new_unique();
+
+    // Same accounts and data as the plain op, with the PDA as the authority
+    let (accounts, op_data) = match op {
+        TokenOp::Transfer => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[0u8][..], &amount].concat(),
+        ),
+        TokenOp::TransferChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[8u8][..], &amount, &[9]].concat(),
+        ),
+        TokenOp::MintTo | TokenOp::MintToChecked => (
+            vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            match op {
+                TokenOp::MintTo => [&[1u8][..], &amount].concat(),
+                _ => [&[18u8][..], &amount, &[9]].concat(),
+            },
+        ),
+        TokenOp::Burn | TokenOp::BurnChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            match op {
+                TokenOp::Burn => [&[2u8][..], &amount].concat(),
+                _ => [&[19u8][..], &amount, &[9]].concat(),
+            },
+        ),
+        TokenOp::Approve => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(delegate, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[3u8][..], &amount].concat(),
+        ),
+        TokenOp::ApproveChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(delegate, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[20u8][..], &amount, &[9]].concat(),
+        ),
+        TokenOp::Revoke => (
+            vec![
+                AccountMeta::new(source_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            vec![4u8],
+        ),
+        TokenOp::FreezeAccount | TokenOp::ThawAccount => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            match op {
+                TokenOp::FreezeAccount => vec![6u8],
+                _ => vec![7u8],
+            },
+        ),
+        TokenOp::CloseAccount => (
+            // The empty destination account is closed into the payer
+            vec![
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new(payer.pubkey(), false),
+                authority_meta,
+                token_program_meta,
+            ],
+            vec![5u8],
+        ),
+        TokenOp::SetAuthority(authority_type) => (
+            vec![
+                AccountMeta::new(mint, false),
+                authority_meta,
+                AccountMeta::new_readonly(new_authority, false),
+                token_program_meta,
+            ],
+            vec![14u8, authority_type, 1],
+        ),
+        _ => unreachable!("{:?} takes no authority", op),
+    };
+
+    let instruction = Instruction {
+        program_id: TOKEN_OPS_PROGRAM_ID,
+        accounts,
+        data: [prefix, op_data].concat(),
+    };
+    let signers: &[&Keypair] = if is_signer { &[&keypair] } else { &[] };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, signers) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for PDA-signed {:?}: {:?}", op, e),
+    };
+
+    let expected = match op {
+        TokenOp::Transfer | TokenOp::TransferChecked => vec![
+            Expect::Amount(source_token, SUPPLY - AMOUNT),
+            Expect::Amount(dest_token, AMOUNT),
+        ],
+        TokenOp::MintTo | TokenOp::MintToChecked => vec![
+            Expect::Supply(mint, SUPPLY + AMOUNT),
+            Expect::Amount(dest_token, AMOUNT),
+        ],
+        TokenOp::Burn | TokenOp::BurnChecked => vec![
+            Expect::Supply(mint, SUPPLY - AMOUNT),
+            Expect::Amount(source_token, SUPPLY - AMOUNT),
+        ],
+        TokenOp::Approve | TokenOp::ApproveChecked => {
+            vec![Expect::Delegate(source_token, Some((delegate, AMOUNT)))]
+        }
+        TokenOp::Revoke => vec![Expect::Delegate(source_token, None)],
+        TokenOp::FreezeAccount => vec![Expect::State(source_token, AccountState::Frozen)],
+        TokenOp::ThawAccount => vec![Expect::State(source_token, AccountState::Initialized)],
+        TokenOp::CloseAccount => vec![Expect::Closed(dest_token)],
+        // Every SetAuthority case targets the mint, so only the mint and
+        // freeze authorities apply
+        TokenOp::SetAuthority(0) => vec![Expect::MintAuthority(mint, Some(new_authority))],
+        TokenOp::SetAuthority(_) => vec![Expect::FreezeAuthority(mint, Some(new_authority))],
+        _ => unreachable!("{:?} takes no authority", op),
+    };
+    if let Err(e) = verify_post_state(&svm, Backend::Token, &expected) {
+        panic!("Post-state mismatch for PDA-signed {:?}: {}", op, e);
+    }
+    cu
+}
+
+pub fn benchmark_pda_signed(token_ops_bytes: &[u8]) {
+    let default_pda = Authority::Pda {
+        seed_count: 1,
+        seed_len: 32,
+    };
+
+    println!(
+        "{:<32} {:>12} {:>12} {:>12}",
+        "Operation", "Keypair CU", "PDA CU", "Overhead"
+    );
+    println!("{}", "-".repeat(71));
+    for &op in PDA_OPS {
+        let keypair_cu = run_pda_benchmark(token_ops_bytes, op, Authority::Keypair);
+        let pda_cu = run_pda_benchmark(token_ops_bytes, op, default_pda);
+        println!(
+            "{:<32} {:>12} {:>12} {:>12}",
+            format!("{:?}", op),
+            keypair_cu,
+            pda_cu,
+            pda_cu as i64 - keypair_cu as i64
+        );
+    }
+    println!("(PDA: 1 seed of 32 bytes plus the bump)");
+
+    println!("\nPDA-signed Transfer by seed configuration (CU):\n");
+    print!("{:<12}", "Seeds");
+    for seed_len in SEED_LENS {
+        print!(" {:>12}", format!("{} bytes", seed_len));
+    }
+    println!();
+    println!("{}", "-".repeat(12 + 13 * SEED_LENS.len()));
+    for &seed_count in SEED_COUNTS {
+        print!("{:<12}", seed_count);
+        for &seed_len in SEED_LENS {
+            let authority = Authority::Pda {
+                seed_count,
+                seed_len,
+            };
+            print!(
+                " {:>12}",
+                run_pda_benchmark(token_ops_bytes, TokenOp::Transfer, authority)
+            );
+        }
+        println!();
+    }
+}
@@ -0,0 +1,152 @@
+//! Return-data query benchmarks: GetAccountDataSize, AmountToUiAmount and
+//! UiAmountToAmount through both token-ops programs, with and without
+//! reading and validating the return data afterwards.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token::state::Account as TokenAccount;
+
+use crate::{fixture_account, fixtures::MintBuilder, load_program, send_instructions, Backend};
+
+/// Mint decimals every query runs against
+const DECIMALS: u8 = 9;
+
+/// Amounts converted by AmountToUiAmount
+const AMOUNTS: &[u64] = &[1, 1_500_000_000, u64::MAX];
+
+/// UI amount string lengths converted by UiAmountToAmount
+const UI_AMOUNT_LENS: &[usize] = &[1, 3, 8, 16, 32, 64, 128];
+
+#[derive(Clone, Debug)]
+enum Query {
+    GetAccountDataSize,
+    AmountToUiAmount(u64),
+    UiAmountToAmount(usize),
+}
+
+impl Query {
+    fn label(&self) -> String {
+        match self {
+            Query::GetAccountDataSize => "GetAccountDataSize".to_string(),
+            Query::AmountToUiAmount(amount) => format!("AmountToUiAmount({})", amount),
+            Query::UiAmountToAmount(len) => format!("UiAmountToAmount({} bytes)", len),
+        }
+    }
+
+    /// token-ops instruction data; `read` asks token-ops to read and
+    /// validate the return data after the CPI
+    fn instruction_data(&self, read: bool) -> Vec<u8> {
+        match self {
+            Query::GetAccountDataSize => {
+                let mut data = vec![28u8, read as u8]; // discriminator for GetAccountDataSize
+                data.extend_from_slice(&(TokenAccount::LEN as u64).to_le_bytes());
+                data
+            }
+            Query::AmountToUiAmount(amount) => {
+                let mut data = vec![29u8, read as u8]; // discriminator for AmountToUiAmount
+                data.extend_from_slice(&amount.to_le_bytes());
+                let expected = spl_token::amount_to_ui_amount_string_trimmed(*amount, DECIMALS);
+                data.extend_from_slice(expected.as_bytes());
+                data
+            }
+            Query::UiAmountToAmount(len) => {
+                let (ui_amount, expected) = ui_amount(*len);
+                let mut data = vec![30u8, read as u8]; // discriminator for UiAmountToAmount
+                data.extend_from_slice(&expected.to_le_bytes());
+                data.extend_from_slice(ui_amount.as_bytes());
+                data
+            }
+        }
+    }
+}
+
+/// A `len`-byte UI amount string and the amount it converts to: "1", or
+/// "1.5" left-padded with zeros
+fn ui_amount(len: usize) -> (String, u64) {
+    if len == 1 {
+        ("1".to_string(), 1_000_000_000)
+    } else {
+        assert!(len >= 3, "UI amount length {} is too short", len);
+        (format!("{:0>width$}", "1.5", width = len), 1_500_000_000)
+    }
+}
+
+fn run_query_benchmark(token_ops_bytes: &[u8], backend: Backend, query: &Query, read: bool) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&payer.pubkey())
+        .decimals(DECIMALS)
+        .supply(1_000_000_000)
+        .build();
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts: vec![
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(backend.token_program(), false),
+        ],
+        data: query.instruction_data(read),
+    };
+    match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {}: {:?}", query.label(), e),
+    }
+}
+
+pub fn benchmark_return_data() {
+    let (Some(token_ops_bytes), Some(token_ops_2022_bytes)) =
+        (load_program("token-ops"), load_program("token-ops-2022"))
+    else {
+        return;
+    };
+
+    println!(
+        "{:<36} {:>10} {:>10} {:>10} {:>10}",
+        "Query", "Token CPI", "+ read", "T22 CPI", "+ read"
+    );
+    println!("{}", "-".repeat(80));
+
+    let queries = std::iter::once(Query::GetAccountDataSize)
+        .chain(
+            AMOUNTS
+                .iter()
+                .map(|&amount| Query::AmountToUiAmount(amount)),
+        )
+        .chain(
+            UI_AMOUNT_LENS
+                .iter()
+                .map(|&len| Query::UiAmountToAmount(len)),
+        );
+    for query in queries {
+        let token_cpi = run_query_benchmark(&token_ops_bytes, Backend::Token, &query, false);
+        let token_read = run_query_benchmark(&token_ops_bytes, Backend::Token, &query, true);
+        let token_2022_cpi =
+            run_query_benchmark(&token_ops_2022_bytes, Backend::Token2022, &query, false);
+        let token_2022_read =
+            run_query_benchmark(&token_ops_2022_bytes, Backend::Token2022, &query, true);
+
+        // The "+ read" columns are the cost of reading and validating the return data
+        println!(
+            "{:<36} {:>10} {:>10} {:>10} {:>10}",
+            query.label(),
+            token_cpi,
+            token_read as i64 - token_cpi as i64,
+            token_2022_cpi,
+            token_2022_read as i64 - token_2022_cpi as i64
+        );
+    }
+}
@@ -0,0 +1,509 @@
+//! Scaling benchmarks: how CU grows with the shape of the instruction rather
+//! than with the operation it performs.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{
+        pad_token_2022_data, MintBuilder, TokenAccountBuilder, ACCOUNT_TYPE_ACCOUNT,
+        ACCOUNT_TYPE_MINT,
+    },
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TOKEN_2022_PROGRAM_ID, TOKEN_OPS_2022_PROGRAM_ID, TOKEN_OPS_PROGRAM_ID,
+    WRITE_COPY_PROGRAM_ID, WRITE_LOOP_PROGRAM_ID,
+};
+
+/// Extra account counts benchmarked on top of each instruction's own accounts.
+/// LiteSVM enforces neither the packet size nor the account lock limit, so
+/// the larger counts fit in a legacy transaction here.
+const EXTRA_ACCOUNT_COUNTS: &[usize] = &[0, 1, 2, 4, 8, 16, 32, 64];
+
+/// Instruction data lengths benchmarked, up to roughly what fits in a transaction
+const INSTRUCTION_DATA_LENS: &[usize] = &[0, 16, 64, 128, 256, 512, 768, 1024, 1200];
+
+/// Data lengths given to every mint and token account in the data-size cases;
+/// `None` keeps the plain 82-byte mint and 165-byte token account layouts
+const ACCOUNT_DATA_LENS: &[Option<usize>] = &[
+    None,
+    Some(170),
+    Some(1024),
+    Some(4096),
+    Some(10_240),
+    Some(20_480),
+    Some(40_960),
+    Some(65_536),
+];
+
+/// token-ops-2022 operations run for each account data length
+#[derive(Clone, Copy)]
+enum DataSizeOp {
+    Transfer,
+    TransferChecked,
+    MintTo,
+    Burn,
+}
+
+/// Builds `count` extra account metas cycling through a writable account, a
+/// read-only account, a duplicate of `duplicate_of` and a read-only signer.
+/// Returns the metas and the keypairs of the signers among them.
+fn extra_accounts(
+    svm: &mut LiteSVM,
+    count: usize,
+    duplicate_of: &AccountMeta,
+) -> (Vec<AccountMeta>, Vec<Keypair>) {
+    let mut metas = Vec::with_capacity(count);
+    let mut signers = Vec::new();
+
+    for i in 0..count {
+        let meta = match i % 4 {
+            0 | 1 => {
+                // Empty system accounts keep data copying out of the per-account cost
+                let pubkey = Pubkey::new_unique();
+                svm.airdrop(&pubkey, 1_000_000_000).unwrap();
+                if i % 4 == 0 {
+                    AccountMeta::new(pubkey, false)
+                } else {
+                    AccountMeta::new_readonly(pubkey, false)
+                }
+            }
+            2 => duplicate_of.clone(),
+            _ => {
+                let signer = Keypair::new();
+                let meta = AccountMeta::new_readonly(signer.pubkey(), true);
+                signers.push(signer);
+                meta
+            }
+        };
+        metas.push(meta);
+    }
+
+    (metas, signers)
+}
+
+pub fn benchmark_account_count() {
+    let (Some(write_loop_bytes), Some(write_copy_bytes), Some(token_ops_bytes)) = (
+        load_program("write-loop"),
+        load_program("write-copy"),
+        load_program("token-ops"),
+    ) else {
+        return;
+    };
+
+    // "ignored" passes the extras to a plain Transfer, which only pays for
+    // deserializing them; "forwarded" also marshals them into the CPI
+    println!(
+        "{:>6} {:>12} {:>12} {:>16} {:>16}",
+        "Extra", "write-loop", "write-copy", "CPI (ignored)", "CPI (forwarded)"
+    );
+    println!("{}", "-".repeat(66));
+
+    let mut rows = Vec::with_capacity(EXTRA_ACCOUNT_COUNTS.len());
+    for &extra in EXTRA_ACCOUNT_COUNTS {
+        let row = [
+            run_write_with_extra_accounts(&write_loop_bytes, WRITE_LOOP_PROGRAM_ID, extra),
+            run_write_with_extra_accounts(&write_copy_bytes, WRITE_COPY_PROGRAM_ID, extra),
+            run_transfer_with_extra_accounts(&token_ops_bytes, extra, false),
+            run_transfer_with_extra_accounts(&token_ops_bytes, extra, true),
+        ];
+        println!(
+            "{:>6} {:>12} {:>12} {:>16} {:>16}",
+            extra, row[0], row[1], row[2], row[3]
+        );
+        rows.push((extra, row));
+    }
+
+    // Average growth per extra account between the smallest and largest case
+    if let (Some(&(first_extra, first)), Some(&(last_extra, last))) = (rows.first(), rows.last()) {
+        let span = (last_extra - first_extra) as f64;
+        let slope = |column: usize| (last[column] as f64 - first[column] as f64) / span;
+        println!(
+            "{:>6} {:>12.1} {:>12.1} {:>16.1} {:>16.1}",
+            "CU/acc",
+            slope(0),
+            slope(1),
+            slope(2),
+            slope(3)
+        );
+    }
+}
+
+fn run_write_with_extra_accounts(program_bytes: &[u8], program_id: Pubkey, extra: usize) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(program_id, program_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let account_pubkey = Pubkey::new_unique();
+    svm.set_account(account_pubkey, fixture_account(program_id, vec![0u8; 100]))
+        .unwrap();
+
+    let account = AccountMeta::new(account_pubkey, false);
+    let (extra_metas, extra_signers) = extra_accounts(&mut svm, extra, &account);
+    let mut accounts = vec![account];
+    accounts.extend(extra_metas);
+
+    let instruction = Instruction {
+        program_id,
+        accounts,
+        data: vec![],
+    };
+    let signers: Vec<&Keypair> = extra_signers.iter().collect();
+    match send_instructions(&mut svm, &[instruction], &payer, &signers) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {} extra accounts: {:?}", extra, e),
+    }
+}
+
+fn run_transfer_with_extra_accounts(token_ops_bytes: &[u8], extra: usize, forward: bool) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(spl_token::ID, include_bytes!("spl_token.so"));
+    svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+
+    let payer = Keypair::new();
+    let authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&authority.pubkey()).supply(1_000_000_000).build();
+    svm.set_account(mint, fixture_account(spl_token::ID, mint_data))
+        .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .amount(1_000_000_000)
+        .build();
+    svm.set_account(source_token, fixture_account(spl_token::ID, source_data))
+        .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build();
+    svm.set_account(dest_token, fixture_account(spl_token::ID, dest_data))
+        .unwrap();
+
+    let source = AccountMeta::new(source_token, false);
+    let (extra_metas, extra_signers) = extra_accounts(&mut svm, extra, &source);
+    let mut accounts = vec![
+        source,
+        AccountMeta::new(dest_token, false),
+        AccountMeta::new_readonly(authority.pubkey(), true),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    accounts.extend(extra_metas);
+
+    // Transfer ignores trailing accounts, TransferWithRemaining forwards them
+    let mut data = vec![if forward { 16u8 } else { 0u8 }];
+    data.extend_from_slice(&1000u64.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: TOKEN_OPS_PROGRAM_ID,
+        accounts,
+        data,
+    };
+    let mut signers = vec![&authority];
+    signers.extend(extra_signers.iter());
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &signers) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for Transfer with {} extra accounts (forwarded: {}): {:?}",
+            extra, forward, e
+        ),
+    };
+
+    let expected = [
+        Expect::Amount(source_token, 1_000_000_000 - 1000),
+        Expect::Amount(dest_token, 1000),
+    ];
+    if let Err(e) = verify_post_state(&svm, Backend::Token, &expected) {
+        panic!(
+            "Post-state mismatch for Transfer with {} extra accounts (forwarded: {}): {}",
+            extra, forward, e
+        );
+    }
+    cu
+}
+
+pub fn benchmark_account_data_size() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!(
+        "{:>10} {:>12} {:>16} {:>12} {:>12}",
+        "Data len", "Transfer", "TransferChecked", "MintTo", "Burn"
+    );
+    println!("{}", "-".repeat(66));
+
+    let ops = [
+        DataSizeOp::Transfer,
+        DataSizeOp::TransferChecked,
+        DataSizeOp::MintTo,
+        DataSizeOp::Burn,
+    ];
+    let mut rows = Vec::with_capacity(ACCOUNT_DATA_LENS.len());
+    for &data_len in ACCOUNT_DATA_LENS {
+        let row = ops.map(|op| run_token_2022_with_data_len(&token_ops_2022_bytes, op, data_len));
+        let label = data_len.map_or("base".to_string(), |len| len.to_string());
+        println!(
+            "{:>10} {:>12} {:>16} {:>12} {:>12}",
+            label, row[0], row[1], row[2], row[3]
+        );
+        rows.push((data_len.unwrap_or(165), row));
+    }
+
+    // Average growth per KiB of account data between the smallest and largest case
+    if let (Some(&(first_len, first)), Some(&(last_len, last))) = (rows.first(), rows.last()) {
+        let kib = (last_len - first_len) as f64 / 1024.0;
+        let slope = |column: usize| (last[column] as f64 - first[column] as f64) / kib;
+        println!(
+            "{:>10} {:>12.1} {:>16.1} {:>12.1} {:>12.1}",
+            "CU/KiB",
+            slope(0),
+            slope(1),
+            slope(2),
+            slope(3)
+        );
+    }
+}
+
+fn run_token_2022_with_data_len(
+    token_ops_bytes: &[u8],
+    op: DataSizeOp,
+    data_len: Option<usize>,
+) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(TOKEN_2022_PROGRAM_ID, include_bytes!("spl_token_2022.so"));
+    svm.add_program(TOKEN_OPS_2022_PROGRAM_ID, token_ops_bytes);
+
+    let payer = Keypair::new();
+    let authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let pad = |data: Vec<u8>, account_type: u8| match data_len {
+        Some(len) => pad_token_2022_data(data, account_type, len),
+        None => data,
+    };
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&authority.pubkey()).supply(1_000_000_000).build();
+    svm.set_account(
+        mint,
+        fixture_account(TOKEN_2022_PROGRAM_ID, pad(mint_data, ACCOUNT_TYPE_MINT)),
+    )
+    .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .amount(1_000_000_000)
+        .build();
+    svm.set_account(
+        source_token,
+        fixture_account(TOKEN_2022_PROGRAM_ID, pad(source_data, ACCOUNT_TYPE_ACCOUNT)),
+    )
+    .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build();
+    svm.set_account(
+        dest_token,
+        fixture_account(TOKEN_2022_PROGRAM_ID, pad(dest_data, ACCOUNT_TYPE_ACCOUNT)),
+    )
+    .unwrap();
+
+    let (accounts, mut data, expected) = match op {
+        DataSizeOp::Transfer => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ],
+            vec![0u8], // discriminator for Transfer
+            [
+                Expect::Amount(source_token, 1_000_000_000 - 1000),
+                Expect::Amount(dest_token, 1000),
+            ],
+        ),
+        DataSizeOp::TransferChecked => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ],
+            vec![8u8], // discriminator for TransferChecked
+            [
+                Expect::Amount(source_token, 1_000_000_000 - 1000),
+                Expect::Amount(dest_token, 1000),
+            ],
+        ),
+        DataSizeOp::MintTo => (
+            vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new(dest_token, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ],
+            vec![1u8], // discriminator for MintTo
+            [
+                Expect::Supply(mint, 1_000_000_000 + 1000),
+                Expect::Amount(dest_token, 1000),
+            ],
+        ),
+        DataSizeOp::Burn => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ],
+            vec![2u8], // discriminator for Burn
+            [
+                Expect::Supply(mint, 1_000_000_000 - 1000),
+                Expect::Amount(source_token, 1_000_000_000 - 1000),
+            ],
+        ),
+    };
+    data.extend_from_slice(&1000u64.to_le_bytes());
+    if let DataSizeOp::TransferChecked = op {
+        data.push(9); // decimals
+    }
+
+    let instruction = Instruction {
+        program_id: TOKEN_OPS_2022_PROGRAM_ID,
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[&authority]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for data length {:?} (Token-2022): {:?}",
+            data_len, e
+        ),
+    };
+
+    if let Err(e) = verify_post_state(&svm, Backend::Token2022, &expected) {
+        panic!(
+            "Post-state mismatch for data length {:?} (Token-2022): {}",
+            data_len, e
+        );
+    }
+    cu
+}
+
+pub fn benchmark_instruction_data_len() {
+    let (Some(write_loop_bytes), Some(write_copy_bytes), Some(token_ops_bytes)) = (
+        load_program("write-loop"),
+        load_program("write-copy"),
+        load_program("token-ops"),
+    ) else {
+        return;
+    };
+
+    // The write programs ignore their instruction data, so their columns are
+    // the runtime's cost of the payload; "CPI forward" has token-ops pass the
+    // same payload on to write-copy
+    println!(
+        "{:>10} {:>12} {:>12} {:>14}",
+        "Data len", "write-loop", "write-copy", "CPI forward"
+    );
+    println!("{}", "-".repeat(51));
+
+    let mut rows = Vec::with_capacity(INSTRUCTION_DATA_LENS.len());
+    for &data_len in INSTRUCTION_DATA_LENS {
+        let row = [
+            run_write_with_data_len(&write_loop_bytes, WRITE_LOOP_PROGRAM_ID, data_len),
+            run_write_with_data_len(&write_copy_bytes, WRITE_COPY_PROGRAM_ID, data_len),
+            run_forward_with_data_len(&token_ops_bytes, &write_copy_bytes, data_len),
+        ];
+        println!(
+            "{:>10} {:>12} {:>12} {:>14}",
+            data_len, row[0], row[1], row[2]
+        );
+        rows.push((data_len, row));
+    }
+
+    // Average growth per byte of instruction data between the smallest and largest case
+    if let (Some(&(first_len, first)), Some(&(last_len, last))) = (rows.first(), rows.last()) {
+        let span = (last_len - first_len) as f64;
+        let slope = |column: usize| (last[column] as f64 - first[column] as f64) / span;
+        println!(
+            "{:>10} {:>12.3} {:>12.3} {:>14.3}",
+            "CU/byte",
+            slope(0),
+            slope(1),
+            slope(2)
+        );
+    }
+}
+
+/// Payload of `len` bytes; the contents are irrelevant to every callee
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+fn run_write_with_data_len(program_bytes: &[u8], program_id: Pubkey, data_len: usize) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(program_id, program_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let account_pubkey = Pubkey::new_unique();
+    svm.set_account(account_pubkey, fixture_account(program_id, vec![0u8; 100]))
+        .unwrap();
+
+    let instruction = Instruction {
+        program_id,
+        accounts: vec![AccountMeta::new(account_pubkey, false)],
+        data: payload(data_len),
+    };
+    match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {} data bytes: {:?}", data_len, e),
+    }
+}
+
+fn run_forward_with_data_len(token_ops_bytes: &[u8], callee_bytes: &[u8], data_len: usize) -> u64 {
+    let mut svm = LiteSVM::new();
+    svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+    svm.add_program(WRITE_COPY_PROGRAM_ID, callee_bytes);
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let account_pubkey = Pubkey::new_unique();
+    svm.set_account(
+        account_pubkey,
+        fixture_account(WRITE_COPY_PROGRAM_ID, vec![0u8; 100]),
+    )
+    .unwrap();
+
+    let mut data = vec![17u8]; // discriminator for Forward
+    data.extend_from_slice(&payload(data_len));
+
+    let instruction = Instruction {
+        program_id: TOKEN_OPS_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(WRITE_COPY_PROGRAM_ID, false),
+            AccountMeta::new(account_pubkey, false),
+        ],
+        data,
+    };
+    match send_instructions(&mut svm, &[instruction], &payer, &[]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for Forward with {} data bytes: {:?}",
+            data_len, e
+        ),
+    }
+}
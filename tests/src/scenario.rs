@@ -0,0 +1,97 @@
+//! Step-by-step scenarios: a sequence of token-ops instructions run against
+//! one SVM, each with the outcome it must have and the post-state it must
+//! leave, reporting the CU of every step.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token_2022::error::TokenError;
+
+use crate::{
+    is_token_error, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// One scenario step: a token-ops instruction, the outcome it must have and
+/// the post-state it must leave
+pub struct Step {
+    pub label: &'static str,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+    /// Token-2022 error the step must fail with, `None` if it must succeed
+    pub error: Option<TokenError>,
+    pub expected: Vec<Expect>,
+}
+
+/// Label, expected outcome and CU of a step
+pub type StepResult = (&'static str, String, u64);
+
+/// Runs `steps` in order as `backend`'s token-ops instructions paid by
+/// `payer`, each signed by whichever of `keypairs` its accounts mark as
+/// signers, and returns every step's result. Panics on the first step with an
+/// unexpected outcome or post-state.
+pub fn run_steps(
+    svm: &mut LiteSVM,
+    backend: Backend,
+    payer: &Keypair,
+    keypairs: &[&Keypair],
+    steps: Vec<Step>,
+) -> Vec<StepResult> {
+    let mut results = Vec::with_capacity(steps.len());
+    for step in steps {
+        let outcome = step
+            .error
+            .as_ref()
+            .map_or("ok".to_string(), |error| format!("{:?}", error));
+
+        let signers: Vec<&Keypair> = keypairs
+            .iter()
+            .copied()
+            .filter(|keypair| {
+                step.accounts
+                    .iter()
+                    .any(|meta| meta.is_signer && meta.pubkey == keypair.pubkey())
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: backend.token_ops(),
+            accounts: step.accounts,
+            data: step.data,
+        };
+        // Scenarios retry an identical instruction once its outcome changes
+        svm.expire_blockhash();
+        let cu = match (
+            send_instructions(svm, &[instruction], payer, &signers),
+            step.error,
+        ) {
+            (Ok(tx_result), None) => tx_result.compute_units_consumed,
+            (Err(e), Some(error)) if is_token_error(&e, error.clone()) => {
+                e.meta.compute_units_consumed
+            }
+            (Ok(_), Some(error)) => panic!(
+                "{} unexpectedly succeeded instead of {:?}",
+                step.label, error
+            ),
+            (Err(e), _) => panic!("{} failed: {:?}", step.label, e),
+        };
+        if let Err(e) = verify_post_state(svm, backend, &step.expected) {
+            panic!("Post-state mismatch for {}: {}", step.label, e);
+        }
+        results.push((step.label, outcome, cu));
+    }
+    results
+}
+
+/// Prints one row per step with its expected outcome and CU
+pub fn print_results(results: &[StepResult]) {
+    println!("{:<45} {:>16} {:>12}", "Step", "Outcome", "CU Consumed");
+    println!("{}", "-".repeat(75));
+
+    for (label, outcome, cu) in results {
+        println!("{:<45} {:>16} {:>12}", label, outcome, cu);
+    }
+}
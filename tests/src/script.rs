@@ -0,0 +1,276 @@
+//! Scripted multi-CPI benchmarks: several token CPIs executed by a single
+//! token-ops Script instruction (discriminator 15).
+
+use litesvm::LiteSVM;
+use solana_compute_budget::compute_budget::ComputeBudget;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, TokenAccountBuilder},
+    send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TOKEN_OPS_PROGRAM_ID,
+};
+
+/// Transfer counts benchmarked for the N-transfer script
+const TRANSFER_COUNTS: &[usize] = &[1, 2, 4, 8, 16, 32];
+
+/// Amount moved or approved by every step
+const STEP_AMOUNT: u64 = 1000;
+
+/// Supply of the fixture mint, all of it held by the source account
+const SUPPLY: u64 = 1_000_000_000;
+
+// Indices into `ScriptFixture::accounts`
+const SOURCE: u8 = 0;
+const DESTINATION: u8 = 1;
+const AUTHORITY: u8 = 2;
+const DELEGATE: u8 = 3;
+const TOKEN_PROGRAM: u8 = 4;
+
+/// One script step: indices into the instruction's accounts plus the
+/// instruction data of a single token-ops operation
+struct ScriptStep {
+    accounts: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl ScriptStep {
+    fn transfer(authority: u8) -> Self {
+        let mut data = vec![0u8]; // discriminator for Transfer
+        data.extend_from_slice(&STEP_AMOUNT.to_le_bytes());
+        Self {
+            accounts: vec![SOURCE, DESTINATION, authority, TOKEN_PROGRAM],
+            data,
+        }
+    }
+
+    fn approve() -> Self {
+        let mut data = vec![3u8]; // discriminator for Approve
+        data.extend_from_slice(&STEP_AMOUNT.to_le_bytes());
+        Self {
+            accounts: vec![SOURCE, DELEGATE, AUTHORITY, TOKEN_PROGRAM],
+            data,
+        }
+    }
+
+    fn revoke() -> Self {
+        Self {
+            accounts: vec![SOURCE, AUTHORITY, TOKEN_PROGRAM],
+            data: vec![4u8], // discriminator for Revoke
+        }
+    }
+}
+
+/// Encodes `steps` as token-ops Script instruction data
+fn encode_script(steps: &[ScriptStep]) -> Vec<u8> {
+    let mut data = vec![15u8, steps.len() as u8];
+    for step in steps {
+        data.push(step.accounts.len() as u8);
+        data.extend_from_slice(&step.accounts);
+        data.push(step.data.len() as u8);
+        data.extend_from_slice(&step.data);
+    }
+    data
+}
+
+/// Mint and token accounts shared by every script case
+struct ScriptFixture {
+    svm: LiteSVM,
+    payer: Keypair,
+    authority: Keypair,
+    delegate: Keypair,
+    source_token: Pubkey,
+    dest_token: Pubkey,
+}
+
+impl ScriptFixture {
+    fn new(token_ops_bytes: &[u8]) -> Self {
+        // Long scripts exceed the default 200k CU limit; raising it on the
+        // SVM keeps a ComputeBudget instruction out of the measured totals
+        let mut svm = LiteSVM::new().with_compute_budget(ComputeBudget {
+            compute_unit_limit: 1_400_000,
+            ..ComputeBudget::default()
+        });
+        svm.add_program(spl_token::ID, include_bytes!("spl_token.so"));
+        svm.add_program(TOKEN_OPS_PROGRAM_ID, token_ops_bytes);
+
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let delegate = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        let mint = Pubkey::new_unique();
+        let mint_data = MintBuilder::new(&authority.pubkey())
+            .freeze_authority(&authority.pubkey())
+            .supply(SUPPLY)
+            .build();
+        svm.set_account(mint, fixture_account(spl_token::ID, mint_data))
+            .unwrap();
+
+        let source_token = Pubkey::new_unique();
+        let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+            .amount(SUPPLY)
+            .build();
+        svm.set_account(source_token, fixture_account(spl_token::ID, source_data))
+            .unwrap();
+
+        let dest_token = Pubkey::new_unique();
+        let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey()).build();
+        svm.set_account(dest_token, fixture_account(spl_token::ID, dest_data))
+            .unwrap();
+
+        Self {
+            svm,
+            payer,
+            authority,
+            delegate,
+            source_token,
+            dest_token,
+        }
+    }
+
+    /// Accounts script steps index into: [source, destination, authority, delegate, token_program]
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_token, false),
+            AccountMeta::new(self.dest_token, false),
+            AccountMeta::new_readonly(self.authority.pubkey(), true),
+            AccountMeta::new_readonly(self.delegate.pubkey(), true),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ]
+    }
+
+    /// Runs a step as a standalone single-op instruction
+    fn run_step(&mut self, label: &str, step: &ScriptStep) -> u64 {
+        let all_accounts = self.accounts();
+        let accounts = step
+            .accounts
+            .iter()
+            .map(|&index| all_accounts[index as usize].clone())
+            .collect();
+        self.run(label, accounts, step.data.clone())
+    }
+
+    /// Runs `steps` as one Script instruction over every fixture account
+    fn run_script(&mut self, label: &str, steps: &[ScriptStep]) -> u64 {
+        let accounts = self.accounts();
+        self.run(label, accounts, encode_script(steps))
+    }
+
+    /// Runs one token-ops instruction and returns the CU it consumed
+    fn run(&mut self, label: &str, accounts: Vec<AccountMeta>, data: Vec<u8>) -> u64 {
+        // Sign with whichever fixture keypairs the instruction marks as signers
+        let signers: Vec<&Keypair> = [&self.authority, &self.delegate]
+            .into_iter()
+            .filter(|keypair| {
+                accounts
+                    .iter()
+                    .any(|meta| meta.is_signer && meta.pubkey == keypair.pubkey())
+            })
+            .collect();
+
+        let instruction = Instruction {
+            program_id: TOKEN_OPS_PROGRAM_ID,
+            accounts,
+            data,
+        };
+        match send_instructions(&mut self.svm, &[instruction], &self.payer, &signers) {
+            Ok(tx_result) => tx_result.compute_units_consumed,
+            Err(e) => panic!("Transaction failed for {}: {:?}", label, e),
+        }
+    }
+
+    /// Checks that exactly `transfers` transfers moved tokens and that no
+    /// delegate is left behind
+    fn verify(&self, label: &str, transfers: u64) {
+        let moved = transfers * STEP_AMOUNT;
+        let expected = [
+            Expect::Amount(self.source_token, SUPPLY - moved),
+            Expect::Amount(self.dest_token, moved),
+            Expect::Delegate(self.source_token, None),
+        ];
+        if let Err(e) = verify_post_state(&self.svm, Backend::Token, &expected) {
+            panic!("Post-state mismatch for {}: {}", label, e);
+        }
+    }
+}
+
+pub fn benchmark_scripts(token_ops_bytes: &[u8]) {
+    println!(
+        "{:<32} {:>12} {:>12} {:>12}",
+        "Script", "Total CU", "CU per CPI", "Marginal CU"
+    );
+    println!("{}", "-".repeat(71));
+
+    // A lone Transfer instruction, as the baseline a script step is compared to
+    let mut fixture = ScriptFixture::new(token_ops_bytes);
+    let single_cu = fixture.run_step("Transfer", &ScriptStep::transfer(AUTHORITY));
+    fixture.verify("Transfer", 1);
+    println!(
+        "{:<32} {:>12} {:>12} {:>12}",
+        "Transfer (single op)", single_cu, single_cu, "-"
+    );
+
+    // N transfers in one instruction; the marginal column is the cost of each
+    // transfer added since the previous row
+    let mut previous: Option<(usize, u64)> = None;
+    for &count in TRANSFER_COUNTS {
+        let label = format!("Transfer x{}", count);
+        let steps: Vec<ScriptStep> = (0..count).map(|_| ScriptStep::transfer(AUTHORITY)).collect();
+        let mut fixture = ScriptFixture::new(token_ops_bytes);
+        let total_cu = fixture.run_script(&label, &steps);
+        fixture.verify(&label, count as u64);
+
+        let marginal = match previous {
+            Some((previous_count, previous_cu)) => {
+                let added = (count - previous_count) as u64;
+                (total_cu.saturating_sub(previous_cu) / added).to_string()
+            }
+            None => "-".to_string(),
+        };
+        println!(
+            "{:<32} {:>12} {:>12} {:>12}",
+            label,
+            total_cu,
+            total_cu / count as u64,
+            marginal
+        );
+        previous = Some((count, total_cu));
+    }
+
+    // Approve -> delegate Transfer -> Revoke, as one script and as three instructions
+    let steps = [
+        ScriptStep::approve(),
+        ScriptStep::transfer(DELEGATE),
+        ScriptStep::revoke(),
+    ];
+    let label = "Approve->Transfer->Revoke";
+    let mut fixture = ScriptFixture::new(token_ops_bytes);
+    let script_cu = fixture.run_script(label, &steps);
+    fixture.verify(label, 1);
+    println!(
+        "{:<32} {:>12} {:>12} {:>12}",
+        label,
+        script_cu,
+        script_cu / steps.len() as u64,
+        "-"
+    );
+
+    let mut fixture = ScriptFixture::new(token_ops_bytes);
+    let separate_cu: u64 = steps.iter().map(|step| fixture.run_step(label, step)).sum();
+    fixture.verify(label, 1);
+    println!(
+        "{:<32} {:>12} {:>12} {:>12}",
+        "  as separate instructions",
+        separate_cu,
+        separate_cu / steps.len() as u64,
+        "-"
+    );
+}
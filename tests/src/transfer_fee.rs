@@ -0,0 +1,312 @@
+//! Transfer-fee extension benchmarks: every Token-2022 transfer-fee
+//! instruction through token-ops-2022, and withdrawing or harvesting the
+//! fees withheld in a growing number of token accounts.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{
+        token_2022_mint_len, AccountExtension, MintBuilder, MintExtension, TokenAccountBuilder,
+    },
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+/// Fee of the fixture mint, in basis points
+const BASIS_POINTS: u16 = 50;
+
+/// Maximum fee of the fixture mint, well above the fee on `AMOUNT`
+const MAXIMUM_FEE: u64 = 1_000_000;
+
+/// Fee SetTransferFee schedules, in basis points
+const NEW_BASIS_POINTS: u16 = 100;
+
+/// Maximum fee SetTransferFee schedules
+const NEW_MAXIMUM_FEE: u64 = 2_000_000;
+
+/// Amount moved by TransferCheckedWithFee
+const AMOUNT: u64 = 1_000_000;
+
+/// Fee withheld on `AMOUNT`: 50 basis points, rounded up
+const FEE: u64 = 5_000;
+
+/// Balance of the fixture source token account
+const SOURCE_BALANCE: u64 = 1_000_000_000;
+
+/// Fees withheld in the mint, or in each withheld-fee source account
+const WITHHELD: u64 = 1_000;
+
+/// Numbers of token accounts to withdraw or harvest withheld fees from
+const WITHHELD_ACCOUNT_COUNTS: &[usize] = &[1, 2, 4, 8, 16, 32];
+
+#[derive(Clone, Copy, Debug)]
+enum TransferFeeOp {
+    InitializeTransferFeeConfig,
+    TransferCheckedWithFee,
+    SetTransferFee,
+    WithdrawWithheldTokensFromMint,
+    /// Withdraw from this many token accounts
+    WithdrawWithheldTokensFromAccounts(usize),
+    /// Harvest from this many token accounts
+    HarvestWithheldTokensToMint(usize),
+}
+
+impl TransferFeeOp {
+    /// Number of token accounts holding withheld fees the op drains
+    fn withheld_accounts(self) -> usize {
+        match self {
+            TransferFeeOp::WithdrawWithheldTokensFromAccounts(count)
+            | TransferFeeOp::HarvestWithheldTokensToMint(count) => count,
+            _ => 0,
+        }
+    }
+}
+
+/// Runs `op` against a mint with a 50 basis point transfer fee, with
+/// `authority` holding every authority and owning every token account
+fn run_transfer_fee_benchmark(token_ops_bytes: &[u8], op: TransferFeeOp) -> u64 {
+    let backend = Backend::Token2022;
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+
+    let payer = Keypair::new();
+    let authority = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let transfer_fee_config = MintExtension::TransferFeeConfig {
+        authority: authority.pubkey(),
+        basis_points: BASIS_POINTS,
+        maximum_fee: MAXIMUM_FEE,
+        withheld_amount: match op {
+            TransferFeeOp::WithdrawWithheldTokensFromMint => WITHHELD,
+            _ => 0,
+        },
+    };
+
+    // InitializeTransferFeeConfig runs on a mint that is not initialized yet
+    let mint = Pubkey::new_unique();
+    let mint_data = match op {
+        TransferFeeOp::InitializeTransferFeeConfig => {
+            vec![0u8; token_2022_mint_len(&[transfer_fee_config])]
+        }
+        _ => MintBuilder::new(&authority.pubkey())
+            .supply(SOURCE_BALANCE)
+            .build_token_2022(&[transfer_fee_config]),
+    };
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let source_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .amount(SOURCE_BALANCE)
+        .build_token_2022(&[AccountExtension::TransferFeeAmount { withheld_amount: 0 }]);
+    svm.set_account(
+        source_token,
+        fixture_account(backend.token_program(), source_data),
+    )
+    .unwrap();
+
+    // Receives the transfer, or the withdrawn fees
+    let dest_token = Pubkey::new_unique();
+    let dest_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+        .build_token_2022(&[AccountExtension::TransferFeeAmount { withheld_amount: 0 }]);
+    svm.set_account(
+        dest_token,
+        fixture_account(backend.token_program(), dest_data),
+    )
+    .unwrap();
+
+    let withheld_tokens: Vec<Pubkey> = (0..op.withheld_accounts())
+        .map(|_| {
+            let withheld_token = Pubkey::new_unique();
+            let withheld_data = TokenAccountBuilder::new(&mint, &authority.pubkey())
+                .build_token_2022(&[AccountExtension::TransferFeeAmount {
+                    withheld_amount: WITHHELD,
+                }]);
+            svm.set_account(
+                withheld_token,
+                fixture_account(backend.token_program(), withheld_data),
+            )
+            .unwrap();
+            withheld_token
+        })
+        .collect();
+    let withheld_metas = withheld_tokens
+        .iter()
+        .map(|withheld_token| AccountMeta::new(*withheld_token, false));
+    let withheld_drained = withheld_tokens
+        .iter()
+        .map(|withheld_token| Expect::AccountWithheld(*withheld_token, 0));
+    let withheld_total = WITHHELD * withheld_tokens.len() as u64;
+
+    let authority_meta = AccountMeta::new_readonly(authority.pubkey(), true);
+    let token_program_meta = AccountMeta::new_readonly(backend.token_program(), false);
+
+    let (accounts, data, expected) = match op {
+        TransferFeeOp::InitializeTransferFeeConfig => (
+            vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority.pubkey(), false),
+                AccountMeta::new_readonly(authority.pubkey(), false),
+                token_program_meta,
+            ],
+            [
+                &[31u8][..],
+                &BASIS_POINTS.to_le_bytes(),
+                &MAXIMUM_FEE.to_le_bytes(),
+            ]
+            .concat(),
+            vec![
+                Expect::TransferFee(mint, BASIS_POINTS, MAXIMUM_FEE),
+                Expect::ExtensionAuthority(mint, 4, Some(authority.pubkey())),
+                Expect::ExtensionAuthority(mint, 5, Some(authority.pubkey())),
+            ],
+        ),
+        TransferFeeOp::TransferCheckedWithFee => (
+            vec![
+                AccountMeta::new(source_token, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [&[32u8][..], &AMOUNT.to_le_bytes(), &[9], &FEE.to_le_bytes()].concat(),
+            vec![
+                Expect::Amount(source_token, SOURCE_BALANCE - AMOUNT),
+                Expect::Amount(dest_token, AMOUNT - FEE),
+                Expect::AccountWithheld(dest_token, FEE),
+            ],
+        ),
+        TransferFeeOp::SetTransferFee => (
+            vec![
+                AccountMeta::new(mint, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            [
+                &[36u8][..],
+                &NEW_BASIS_POINTS.to_le_bytes(),
+                &NEW_MAXIMUM_FEE.to_le_bytes(),
+            ]
+            .concat(),
+            vec![Expect::TransferFee(mint, NEW_BASIS_POINTS, NEW_MAXIMUM_FEE)],
+        ),
+        TransferFeeOp::WithdrawWithheldTokensFromMint => (
+            vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ],
+            vec![33u8],
+            vec![
+                Expect::MintWithheld(mint, 0),
+                Expect::Amount(dest_token, WITHHELD),
+            ],
+        ),
+        TransferFeeOp::WithdrawWithheldTokensFromAccounts(_) => {
+            let mut accounts = vec![
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(dest_token, false),
+                authority_meta,
+                token_program_meta,
+            ];
+            accounts.extend(withheld_metas);
+            let mut expected = vec![Expect::Amount(dest_token, withheld_total)];
+            expected.extend(withheld_drained);
+            (accounts, vec![34u8], expected)
+        }
+        TransferFeeOp::HarvestWithheldTokensToMint(_) => {
+            let mut accounts = vec![AccountMeta::new(mint, false), token_program_meta];
+            accounts.extend(withheld_metas);
+            let mut expected = vec![Expect::MintWithheld(mint, withheld_total)];
+            expected.extend(withheld_drained);
+            (accounts, vec![35u8], expected)
+        }
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[&authority]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!("Transaction failed for {:?}: {:?}", op, e),
+    };
+
+    // The fee config is only readable once InitializeMint2 finishes the mint
+    if let TransferFeeOp::InitializeTransferFeeConfig = op {
+        let instruction = Instruction {
+            program_id: backend.token_ops(),
+            accounts: vec![
+                AccountMeta::new(mint, false),
+                AccountMeta::new_readonly(authority.pubkey(), false),
+                AccountMeta::new_readonly(backend.token_program(), false),
+            ],
+            data: vec![10u8, 9, 0],
+        };
+        if let Err(e) = send_instructions(&mut svm, &[instruction], &payer, &[]) {
+            panic!("InitializeMint2 after {:?} failed: {:?}", op, e);
+        }
+    }
+
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!("Post-state mismatch for {:?}: {}", op, e);
+    }
+    cu
+}
+
+pub fn benchmark_transfer_fee() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    println!("{:<45} {:>12}", "Operation", "CU Consumed");
+    println!("{}", "-".repeat(58));
+
+    for op in [
+        TransferFeeOp::InitializeTransferFeeConfig,
+        TransferFeeOp::TransferCheckedWithFee,
+        TransferFeeOp::SetTransferFee,
+        TransferFeeOp::WithdrawWithheldTokensFromMint,
+    ] {
+        let cu = run_transfer_fee_benchmark(&token_ops_2022_bytes, op);
+        println!("{:<45} {:>12}", format!("{:?}", op), cu);
+    }
+
+    // Both drain every source account, so the per-account cost is the slope
+    println!();
+    println!(
+        "{:>16} {:>12} {:>12} {:>12}",
+        "Source accounts", "Withdraw CU", "Harvest CU", "Withdraw/acct"
+    );
+    println!("{}", "-".repeat(55));
+
+    for &count in WITHHELD_ACCOUNT_COUNTS {
+        let withdraw_cu = run_transfer_fee_benchmark(
+            &token_ops_2022_bytes,
+            TransferFeeOp::WithdrawWithheldTokensFromAccounts(count),
+        );
+        let harvest_cu = run_transfer_fee_benchmark(
+            &token_ops_2022_bytes,
+            TransferFeeOp::HarvestWithheldTokensToMint(count),
+        );
+        println!(
+            "{:>16} {:>12} {:>12} {:>12}",
+            count,
+            withdraw_cu,
+            harvest_cu,
+            withdraw_cu / count as u64
+        );
+    }
+}
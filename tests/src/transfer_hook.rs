@@ -0,0 +1,188 @@
+//! Transfer-hook extension benchmarks: TransferChecked through token-ops-2022
+//! on a mint whose transfer hook is the local transfer-hook program, with a
+//! growing number of extra accounts listed in its extra-account-metas PDA,
+//! next to the same transfer on a mint without a hook.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::ExecuteInstruction,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{AccountExtension, MintBuilder, MintExtension, TokenAccountBuilder},
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend, TRANSFER_HOOK_PROGRAM_ID,
+};
+
+/// Amount moved by every transfer
+const AMOUNT: u64 = 1000;
+
+/// Balance of the fixture source token account
+const SOURCE_BALANCE: u64 = 1_000_000_000;
+
+/// Numbers of extra accounts the hook's extra-account-metas PDA lists
+const EXTRA_ACCOUNT_COUNTS: &[usize] = &[0, 1, 2, 4, 8, 16];
+
+/// Data of an extra-account-metas PDA listing `extra_accounts` as read-only
+/// fixed addresses
+fn extra_account_metas_data(extra_accounts: &[Pubkey]) -> Vec<u8> {
+    let metas: Vec<ExtraAccountMeta> = extra_accounts
+        .iter()
+        .map(|address| ExtraAccountMeta::new_with_pubkey(address, false, false).unwrap())
+        .collect();
+    let mut data = vec![0u8; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)
+        .expect("extra account metas do not fit");
+    data
+}
+
+/// Runs TransferChecked on a mint with the local transfer hook and `extra`
+/// extra accounts, or on a mint without a hook when `extra` is `None`
+fn run_transfer_hook_benchmark(
+    token_ops_bytes: &[u8],
+    hook_bytes: &[u8],
+    extra: Option<usize>,
+) -> u64 {
+    let backend = Backend::Token2022;
+    let mut svm = LiteSVM::new();
+    svm.add_program(backend.token_program(), backend.program_bytes());
+    svm.add_program(backend.token_ops(), token_ops_bytes);
+    svm.add_program(TRANSFER_HOOK_PROGRAM_ID, hook_bytes);
+
+    let payer = Keypair::new();
+    let owner = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let mint_extensions: Vec<MintExtension> = extra
+        .map(|_| MintExtension::TransferHook {
+            authority: owner.pubkey(),
+            program_id: TRANSFER_HOOK_PROGRAM_ID,
+        })
+        .into_iter()
+        .collect();
+    let account_extensions = AccountExtension::required_by(&mint_extensions);
+
+    let mint = Pubkey::new_unique();
+    let mint_data = MintBuilder::new(&owner.pubkey())
+        .supply(SOURCE_BALANCE)
+        .build_token_2022(&mint_extensions);
+    svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+        .unwrap();
+
+    let source_token = Pubkey::new_unique();
+    let source_data = TokenAccountBuilder::new(&mint, &owner.pubkey())
+        .amount(SOURCE_BALANCE)
+        .build_token_2022(&account_extensions);
+    svm.set_account(
+        source_token,
+        fixture_account(backend.token_program(), source_data),
+    )
+    .unwrap();
+
+    let dest_token = Pubkey::new_unique();
+    let dest_data =
+        TokenAccountBuilder::new(&mint, &owner.pubkey()).build_token_2022(&account_extensions);
+    svm.set_account(
+        dest_token,
+        fixture_account(backend.token_program(), dest_data),
+    )
+    .unwrap();
+
+    let mut accounts = vec![
+        AccountMeta::new(source_token, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new(dest_token, false),
+        AccountMeta::new_readonly(owner.pubkey(), true),
+        AccountMeta::new_readonly(backend.token_program(), false),
+    ];
+    let data = match extra {
+        // Plain TransferChecked
+        None => [&[8u8][..], &AMOUNT.to_le_bytes(), &[9]].concat(),
+        Some(extra) => {
+            // The hook checks the extra accounts exist only by address
+            let extra_accounts: Vec<Pubkey> = (0..extra).map(|_| Pubkey::new_unique()).collect();
+            let extra_account_metas =
+                get_extra_account_metas_address(&mint, &TRANSFER_HOOK_PROGRAM_ID);
+            svm.set_account(
+                extra_account_metas,
+                fixture_account(
+                    TRANSFER_HOOK_PROGRAM_ID,
+                    extra_account_metas_data(&extra_accounts),
+                ),
+            )
+            .unwrap();
+
+            accounts.push(AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false));
+            accounts.push(AccountMeta::new_readonly(extra_account_metas, false));
+            accounts.extend(
+                extra_accounts
+                    .iter()
+                    .map(|address| AccountMeta::new_readonly(*address, false)),
+            );
+            [&[37u8][..], &AMOUNT.to_le_bytes(), &[9]].concat()
+        }
+    };
+
+    let instruction = Instruction {
+        program_id: backend.token_ops(),
+        accounts,
+        data,
+    };
+    let cu = match send_instructions(&mut svm, &[instruction], &payer, &[&owner]) {
+        Ok(tx_result) => tx_result.compute_units_consumed,
+        Err(e) => panic!(
+            "Transaction failed for TransferChecked ({:?} hook extra accounts): {:?}",
+            extra, e
+        ),
+    };
+
+    let expected = [
+        Expect::Amount(source_token, SOURCE_BALANCE - AMOUNT),
+        Expect::Amount(dest_token, AMOUNT),
+    ];
+    if let Err(e) = verify_post_state(&svm, backend, &expected) {
+        panic!(
+            "Post-state mismatch for TransferChecked ({:?} hook extra accounts): {}",
+            extra, e
+        );
+    }
+    cu
+}
+
+pub fn benchmark_transfer_hook() {
+    let (Some(token_ops_2022_bytes), Some(hook_bytes)) = (
+        load_program("token-ops-2022"),
+        load_program("transfer-hook"),
+    ) else {
+        return;
+    };
+
+    println!(
+        "{:<45} {:>12} {:>12}",
+        "TransferChecked", "CU Consumed", "Hook cost"
+    );
+    println!("{}", "-".repeat(71));
+
+    let plain_cu = run_transfer_hook_benchmark(&token_ops_2022_bytes, &hook_bytes, None);
+    println!("{:<45} {:>12} {:>12}", "no transfer hook", plain_cu, "-");
+
+    // The hook cost covers resolving the extra accounts and the nested CPI
+    for &extra in EXTRA_ACCOUNT_COUNTS {
+        let cu = run_transfer_hook_benchmark(&token_ops_2022_bytes, &hook_bytes, Some(extra));
+        println!(
+            "{:<45} {:>12} {:>+12}",
+            format!("hook, {} extra accounts", extra),
+            cu,
+            cu as i64 - plain_cu as i64
+        );
+    }
+}
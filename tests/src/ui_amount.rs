@@ -0,0 +1,371 @@
+//! UI amount scenarios: AmountToUiAmount and UiAmountToAmount through
+//! token-ops-2022 on an interest-bearing and a scaled UI amount mint, with
+//! the rate or multiplier updated and the LiteSVM clock advanced between
+//! steps, next to the same conversions on a mint without either extension.
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token_2022::{
+    extension::{
+        interest_bearing_mint::InterestBearingConfig, scaled_ui_amount::ScaledUiAmountConfig,
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+
+use crate::{
+    fixture_account,
+    fixtures::{MintBuilder, MintExtension},
+    load_program, send_instructions,
+    verify::{verify_post_state, Expect},
+    Backend,
+};
+
+const DECIMALS: u8 = 9;
+
+/// Amount converted by AmountToUiAmount
+const AMOUNT: u64 = 1_500_000_000;
+
+/// UI amount converted by UiAmountToAmount
+const UI_AMOUNT: &str = "1.5";
+
+/// Initial rate of the interest-bearing mint, in basis points
+const RATE: i16 = 500;
+
+/// Rate UpdateRate sets, in basis points
+const NEW_RATE: i16 = -300;
+
+/// Initial multiplier of the scaled UI amount mint
+const MULTIPLIER: f64 = 1.5;
+
+/// Multiplier UpdateMultiplier schedules
+const NEW_MULTIPLIER: f64 = 2.0;
+
+const DAY: i64 = 86_400;
+
+/// The year Token-2022 compounds interest over, in seconds
+const YEAR: i64 = 31_556_736;
+
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    /// Moves the clock forward by this many seconds
+    AdvanceClock(i64),
+    AmountToUiAmount,
+    UiAmountToAmount,
+    UpdateRate(i16),
+    /// Schedules a multiplier, effective this many seconds from now
+    UpdateMultiplier(f64, i64),
+}
+
+impl Step {
+    fn label(self) -> String {
+        match self {
+            Step::AdvanceClock(seconds) => format!("AdvanceClock({} s)", seconds),
+            Step::AmountToUiAmount => format!("AmountToUiAmount({})", AMOUNT),
+            Step::UiAmountToAmount => format!("UiAmountToAmount({:?})", UI_AMOUNT),
+            Step::UpdateRate(rate) => format!("UpdateRate({})", rate),
+            Step::UpdateMultiplier(multiplier, delay) => {
+                format!("UpdateMultiplier({}, in {} s)", multiplier, delay)
+            }
+        }
+    }
+
+    /// Whether the step's instruction needs the mint's rate or multiplier
+    /// authority to sign
+    fn needs_authority(self) -> bool {
+        matches!(self, Step::UpdateRate(_) | Step::UpdateMultiplier(..))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum UiMint {
+    Plain,
+    InterestBearing,
+    ScaledUiAmount,
+}
+
+impl UiMint {
+    fn extensions(self, authority: &Pubkey) -> Vec<MintExtension> {
+        let authority = *authority;
+        match self {
+            UiMint::Plain => vec![],
+            UiMint::InterestBearing => vec![MintExtension::InterestBearingConfig {
+                authority,
+                rate: RATE,
+            }],
+            UiMint::ScaledUiAmount => vec![MintExtension::ScaledUiAmount {
+                authority,
+                multiplier: MULTIPLIER,
+            }],
+        }
+    }
+
+    /// The interest-bearing conversions run once with only the initial rate
+    /// accrued and once with both rates; the scaled ones before and after
+    /// the scheduled multiplier takes effect
+    fn steps(self) -> &'static [Step] {
+        match self {
+            UiMint::Plain => &[Step::AmountToUiAmount, Step::UiAmountToAmount],
+            UiMint::InterestBearing => &[
+                Step::AdvanceClock(YEAR),
+                Step::AmountToUiAmount,
+                Step::UiAmountToAmount,
+                Step::UpdateRate(NEW_RATE),
+                Step::AdvanceClock(YEAR),
+                Step::AmountToUiAmount,
+                Step::UiAmountToAmount,
+            ],
+            UiMint::ScaledUiAmount => &[
+                Step::AmountToUiAmount,
+                Step::UiAmountToAmount,
+                Step::UpdateMultiplier(NEW_MULTIPLIER, DAY),
+                Step::AmountToUiAmount,
+                Step::AdvanceClock(DAY),
+                Step::AmountToUiAmount,
+                Step::UiAmountToAmount,
+            ],
+        }
+    }
+}
+
+/// A mint of one `UiMint` kind, with the clock starting at 0
+struct Scenario {
+    svm: LiteSVM,
+    payer: Keypair,
+    authority: Keypair,
+    mint: Pubkey,
+}
+
+impl Scenario {
+    fn new(token_ops_bytes: &[u8], ui_mint: UiMint) -> Self {
+        let backend = Backend::Token2022;
+        let mut svm = LiteSVM::new();
+        svm.add_program(backend.token_program(), backend.program_bytes());
+        svm.add_program(backend.token_ops(), token_ops_bytes);
+
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        let mint = Pubkey::new_unique();
+        let mint_data = MintBuilder::new(&authority.pubkey())
+            .decimals(DECIMALS)
+            .supply(AMOUNT)
+            .build_token_2022(&ui_mint.extensions(&authority.pubkey()));
+        svm.set_account(mint, fixture_account(backend.token_program(), mint_data))
+            .unwrap();
+
+        Self {
+            svm,
+            payer,
+            authority,
+            mint,
+        }
+    }
+
+    fn unix_timestamp(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Reads the mint and converts with the same math Token-2022 uses,
+    /// interest first, at the current clock
+    fn convert<T>(
+        &self,
+        interest_bearing: impl FnOnce(&InterestBearingConfig, i64) -> T,
+        scaled: impl FnOnce(&ScaledUiAmountConfig, i64) -> T,
+        plain: impl FnOnce() -> T,
+    ) -> T {
+        let account = self.svm.get_account(&self.mint).unwrap();
+        let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+        let unix_timestamp = self.unix_timestamp();
+        if let Ok(config) = mint.get_extension::<InterestBearingConfig>() {
+            interest_bearing(config, unix_timestamp)
+        } else if let Ok(config) = mint.get_extension::<ScaledUiAmountConfig>() {
+            scaled(config, unix_timestamp)
+        } else {
+            plain()
+        }
+    }
+
+    /// token-ops-2022 instruction of `step` and the post-state it must
+    /// leave; the conversions check their own return data
+    fn instruction(&self, step: Step) -> (Instruction, Vec<Expect>) {
+        let backend = Backend::Token2022;
+        let token_program = AccountMeta::new_readonly(backend.token_program(), false);
+        let authority = AccountMeta::new_readonly(self.authority.pubkey(), true);
+        let (accounts, data, expected) = match step {
+            Step::AdvanceClock(_) => unreachable!("advancing the clock sends no instruction"),
+            Step::AmountToUiAmount => {
+                let expected_ui_amount = self.convert(
+                    |config, now| config.amount_to_ui_amount(AMOUNT, DECIMALS, now).unwrap(),
+                    |config, now| config.amount_to_ui_amount(AMOUNT, DECIMALS, now).unwrap(),
+                    || spl_token_2022::amount_to_ui_amount_string_trimmed(AMOUNT, DECIMALS),
+                );
+                (
+                    vec![AccountMeta::new_readonly(self.mint, false), token_program],
+                    [
+                        &[29u8, 1][..],
+                        &AMOUNT.to_le_bytes(),
+                        expected_ui_amount.as_bytes(),
+                    ]
+                    .concat(),
+                    vec![],
+                )
+            }
+            Step::UiAmountToAmount => {
+                let expected_amount = self.convert(
+                    |config, now| {
+                        config
+                            .try_ui_amount_into_amount(UI_AMOUNT, DECIMALS, now)
+                            .unwrap()
+                    },
+                    |config, now| {
+                        config
+                            .try_ui_amount_into_amount(UI_AMOUNT, DECIMALS, now)
+                            .unwrap()
+                    },
+                    || {
+                        spl_token_2022::try_ui_amount_into_amount(UI_AMOUNT.to_string(), DECIMALS)
+                            .unwrap()
+                    },
+                );
+                (
+                    vec![AccountMeta::new_readonly(self.mint, false), token_program],
+                    [
+                        &[30u8, 1][..],
+                        &expected_amount.to_le_bytes(),
+                        UI_AMOUNT.as_bytes(),
+                    ]
+                    .concat(),
+                    vec![],
+                )
+            }
+            Step::UpdateRate(rate) => (
+                vec![AccountMeta::new(self.mint, false), authority, token_program],
+                [&[48u8][..], &rate.to_le_bytes()].concat(),
+                vec![Expect::InterestRate(self.mint, rate)],
+            ),
+            Step::UpdateMultiplier(multiplier, delay) => {
+                let effective_timestamp = self.unix_timestamp() + delay;
+                (
+                    vec![AccountMeta::new(self.mint, false), authority, token_program],
+                    [
+                        &[49u8][..],
+                        &multiplier.to_le_bytes(),
+                        &effective_timestamp.to_le_bytes(),
+                    ]
+                    .concat(),
+                    vec![Expect::NewMultiplier(
+                        self.mint,
+                        multiplier,
+                        effective_timestamp,
+                    )],
+                )
+            }
+        };
+        let instruction = Instruction {
+            program_id: backend.token_ops(),
+            accounts,
+            data,
+        };
+        (instruction, expected)
+    }
+
+    /// Runs every step in order and returns each instruction step with the
+    /// clock it ran at and its CU
+    fn run(&mut self, ui_mint: UiMint) -> Vec<(Step, i64, u64)> {
+        let mut results = Vec::new();
+        for &step in ui_mint.steps() {
+            if let Step::AdvanceClock(seconds) = step {
+                let mut clock = self.svm.get_sysvar::<Clock>();
+                clock.unix_timestamp += seconds;
+                self.svm.set_sysvar(&clock);
+                continue;
+            }
+            let unix_timestamp = self.unix_timestamp();
+
+            let (instruction, expected) = self.instruction(step);
+            // A repeated conversion would otherwise be a duplicate transaction
+            self.svm.expire_blockhash();
+            let signers: &[&Keypair] = if step.needs_authority() {
+                &[&self.authority]
+            } else {
+                &[]
+            };
+            let cu = match send_instructions(&mut self.svm, &[instruction], &self.payer, signers) {
+                Ok(tx_result) => tx_result.compute_units_consumed,
+                Err(e) => panic!(
+                    "Transaction failed for {} ({:?}): {:?}",
+                    step.label(),
+                    ui_mint,
+                    e
+                ),
+            };
+            if let Err(e) = verify_post_state(&self.svm, Backend::Token2022, &expected) {
+                panic!(
+                    "Post-state mismatch for {} ({:?}): {}",
+                    step.label(),
+                    ui_mint,
+                    e
+                );
+            }
+            results.push((step, unix_timestamp, cu));
+        }
+        results
+    }
+}
+
+pub fn benchmark_ui_amount() {
+    let Some(token_ops_2022_bytes) = load_program("token-ops-2022") else {
+        return;
+    };
+
+    // Conversion cost on a mint without either extension, per step kind
+    let plain = Scenario::new(&token_ops_2022_bytes, UiMint::Plain).run(UiMint::Plain);
+    let plain_cu = |step: Step| {
+        plain
+            .iter()
+            .find(|(plain_step, _, _)| plain_step.label() == step.label())
+            .map(|&(_, _, cu)| cu)
+    };
+
+    for ui_mint in [
+        UiMint::Plain,
+        UiMint::InterestBearing,
+        UiMint::ScaledUiAmount,
+    ] {
+        println!("{:?} mint:", ui_mint);
+        println!(
+            "{:<45} {:>8} {:>12} {:>12}",
+            "Step", "Day", "CU Consumed", "vs plain"
+        );
+        println!("{}", "-".repeat(80));
+
+        let results = match ui_mint {
+            UiMint::Plain => plain.clone(),
+            _ => Scenario::new(&token_ops_2022_bytes, ui_mint).run(ui_mint),
+        };
+        for (step, unix_timestamp, cu) in results {
+            // The surcharge is what the extension's math adds to a conversion
+            let surcharge = match (ui_mint, plain_cu(step)) {
+                (UiMint::Plain, _) => "-".to_string(),
+                (_, Some(plain_cu)) => format!("{:+}", cu as i64 - plain_cu as i64),
+                _ => "-".to_string(),
+            };
+            println!(
+                "{:<45} {:>8} {:>12} {:>12}",
+                step.label(),
+                unix_timestamp / DAY,
+                cu,
+                surcharge
+            );
+        }
+        println!();
+    }
+}
@@ -0,0 +1,395 @@
+//! Post-execution state checks: each benchmark case declares the mint and
+//! token account state its CPI should leave behind, and the harness unpacks
+//! the accounts with `spl_token::state` or the Token-2022 state types and
+//! compares, so a builder that silently does nothing cannot report a number.
+
+use litesvm::LiteSVM;
+use solana_sdk::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::AccountState;
+use spl_token_2022::extension::{
+    confidential_transfer::ConfidentialTransferMint,
+    confidential_transfer_fee::ConfidentialTransferFeeConfig,
+    default_account_state::DefaultAccountState,
+    group_member_pointer::GroupMemberPointer,
+    group_pointer::GroupPointer,
+    interest_bearing_mint::InterestBearingConfig,
+    memo_transfer::MemoTransfer,
+    metadata_pointer::MetadataPointer,
+    mint_close_authority::MintCloseAuthority,
+    pausable::PausableConfig,
+    permanent_delegate::PermanentDelegate,
+    scaled_ui_amount::ScaledUiAmountConfig,
+    transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+    transfer_hook::TransferHook,
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+
+use crate::Backend;
+
+/// One field of the expected post-execution state, keyed by account
+#[derive(Clone, Copy, Debug)]
+pub enum Expect {
+    Supply(Pubkey, u64),
+    MintAuthority(Pubkey, Option<Pubkey>),
+    FreezeAuthority(Pubkey, Option<Pubkey>),
+    Amount(Pubkey, u64),
+    Owner(Pubkey, Pubkey),
+    /// Delegate and delegated amount, `None` for no delegate
+    Delegate(Pubkey, Option<(Pubkey, u64)>),
+    State(Pubkey, AccountState),
+    CloseAuthority(Pubkey, Option<Pubkey>),
+    /// Required and total signers of an initialized multisig
+    Multisig(Pubkey, u8, u8),
+    /// Authority of the Token-2022 mint extension a SetAuthority authority
+    /// type (4 and up) refers to
+    ExtensionAuthority(Pubkey, u8, Option<Pubkey>),
+    /// Basis points and maximum fee of a Token-2022 mint's newer transfer
+    /// fee, the one SetTransferFee schedules
+    TransferFee(Pubkey, u16, u64),
+    /// Fees withheld in a Token-2022 mint
+    MintWithheld(Pubkey, u64),
+    /// Fees withheld in a Token-2022 token account
+    AccountWithheld(Pubkey, u64),
+    /// Whether a Token-2022 token account requires memos on incoming
+    /// transfers
+    MemoRequired(Pubkey, bool),
+    /// A Token-2022 mint carries the extension
+    HasMintExtension(Pubkey, ExtensionType),
+    /// State a Token-2022 mint gives new token accounts
+    DefaultAccountState(Pubkey, AccountState),
+    /// Current rate of a Token-2022 interest-bearing mint, in basis points
+    InterestRate(Pubkey, i16),
+    /// Next multiplier of a Token-2022 scaled UI amount mint, and the unix
+    /// timestamp it takes effect at
+    NewMultiplier(Pubkey, f64, i64),
+    /// Whether a Token-2022 pausable mint is paused
+    Paused(Pubkey, bool),
+    /// Lamports held by an account
+    Lamports(Pubkey, u64),
+    /// The account no longer exists, or holds no lamports
+    Closed(Pubkey),
+}
+
+/// Mint fields both token programs share
+struct MintState {
+    supply: u64,
+    mint_authority: Option<Pubkey>,
+    freeze_authority: Option<Pubkey>,
+}
+
+/// Token account fields both token programs share
+struct TokenAccountState {
+    owner: Pubkey,
+    amount: u64,
+    delegate: Option<Pubkey>,
+    delegated_amount: u64,
+    state: AccountState,
+    close_authority: Option<Pubkey>,
+}
+
+fn account_data(svm: &LiteSVM, address: &Pubkey) -> Result<Vec<u8>, String> {
+    svm.get_account(address)
+        .map(|account| account.data)
+        .ok_or_else(|| format!("account {} does not exist", address))
+}
+
+fn unpack_mint(svm: &LiteSVM, backend: Backend, address: &Pubkey) -> Result<MintState, String> {
+    let data = account_data(svm, address)?;
+    let error = |e| format!("mint {}: {}", address, e);
+    Ok(match backend {
+        Backend::Token => {
+            let mint = spl_token::state::Mint::unpack(&data).map_err(error)?;
+            MintState {
+                supply: mint.supply,
+                mint_authority: mint.mint_authority.into(),
+                freeze_authority: mint.freeze_authority.into(),
+            }
+        }
+        Backend::Token2022 => {
+            let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+                .map_err(error)?
+                .base;
+            MintState {
+                supply: mint.supply,
+                mint_authority: mint.mint_authority.into(),
+                freeze_authority: mint.freeze_authority.into(),
+            }
+        }
+    })
+}
+
+fn unpack_token_account(
+    svm: &LiteSVM,
+    backend: Backend,
+    address: &Pubkey,
+) -> Result<TokenAccountState, String> {
+    let data = account_data(svm, address)?;
+    let error = |e| format!("token account {}: {}", address, e);
+    Ok(match backend {
+        Backend::Token => {
+            let account = spl_token::state::Account::unpack(&data).map_err(error)?;
+            TokenAccountState {
+                owner: account.owner,
+                amount: account.amount,
+                delegate: account.delegate.into(),
+                delegated_amount: account.delegated_amount,
+                state: account.state,
+                close_authority: account.close_authority.into(),
+            }
+        }
+        Backend::Token2022 => {
+            let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+                .map_err(error)?
+                .base;
+            TokenAccountState {
+                owner: account.owner,
+                amount: account.amount,
+                delegate: account.delegate.into(),
+                delegated_amount: account.delegated_amount,
+                // Both programs number the states the same way
+                state: AccountState::try_from(account.state as u8)
+                    .map_err(|e| format!("token account {}: {}", address, e))?,
+                close_authority: account.close_authority.into(),
+            }
+        }
+    })
+}
+
+/// Reads the authority of the Token-2022 mint extension `authority_type`
+/// refers to
+fn extension_authority(
+    svm: &LiteSVM,
+    address: &Pubkey,
+    authority_type: u8,
+) -> Result<Option<Pubkey>, String> {
+    let data = account_data(svm, address)?;
+    let error = |e| format!("mint {}: {}", address, e);
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).map_err(error)?;
+    let authority = match authority_type {
+        4 => {
+            mint.get_extension::<TransferFeeConfig>()
+                .map_err(error)?
+                .transfer_fee_config_authority
+        }
+        5 => {
+            mint.get_extension::<TransferFeeConfig>()
+                .map_err(error)?
+                .withdraw_withheld_authority
+        }
+        6 => {
+            mint.get_extension::<MintCloseAuthority>()
+                .map_err(error)?
+                .close_authority
+        }
+        7 => {
+            mint.get_extension::<InterestBearingConfig>()
+                .map_err(error)?
+                .rate_authority
+        }
+        8 => {
+            mint.get_extension::<PermanentDelegate>()
+                .map_err(error)?
+                .delegate
+        }
+        9 => {
+            mint.get_extension::<ConfidentialTransferMint>()
+                .map_err(error)?
+                .authority
+        }
+        10 => {
+            mint.get_extension::<TransferHook>()
+                .map_err(error)?
+                .authority
+        }
+        11 => {
+            mint.get_extension::<ConfidentialTransferFeeConfig>()
+                .map_err(error)?
+                .authority
+        }
+        12 => {
+            mint.get_extension::<MetadataPointer>()
+                .map_err(error)?
+                .authority
+        }
+        13 => {
+            mint.get_extension::<GroupPointer>()
+                .map_err(error)?
+                .authority
+        }
+        14 => {
+            mint.get_extension::<GroupMemberPointer>()
+                .map_err(error)?
+                .authority
+        }
+        15 => {
+            mint.get_extension::<ScaledUiAmountConfig>()
+                .map_err(error)?
+                .authority
+        }
+        16 => {
+            mint.get_extension::<PausableConfig>()
+                .map_err(error)?
+                .authority
+        }
+        _ => {
+            return Err(format!(
+                "authority type {} has no extension",
+                authority_type
+            ))
+        }
+    };
+    Ok(authority.into())
+}
+
+/// Unpacks a Token-2022 mint with its extensions and reads from it with
+/// `read`
+fn read_token_2022_mint<T>(
+    svm: &LiteSVM,
+    address: &Pubkey,
+    read: impl FnOnce(&StateWithExtensions<spl_token_2022::state::Mint>) -> Result<T, ProgramError>,
+) -> Result<T, String> {
+    let data = account_data(svm, address)?;
+    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+        .and_then(|mint| read(&mint))
+        .map_err(|e| format!("mint {}: {}", address, e))
+}
+
+/// Unpacks a Token-2022 token account with its extensions and reads from it
+/// with `read`
+fn read_token_2022_account<T>(
+    svm: &LiteSVM,
+    address: &Pubkey,
+    read: impl FnOnce(&StateWithExtensions<spl_token_2022::state::Account>) -> Result<T, ProgramError>,
+) -> Result<T, String> {
+    let data = account_data(svm, address)?;
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .and_then(|account| read(&account))
+        .map_err(|e| format!("token account {}: {}", address, e))
+}
+
+/// Checks every expectation against the accounts in `svm`, returning the
+/// first mismatch
+pub fn verify_post_state(
+    svm: &LiteSVM,
+    backend: Backend,
+    expected: &[Expect],
+) -> Result<(), String> {
+    for &expectation in expected {
+        let matches = match expectation {
+            Expect::Supply(mint, supply) => unpack_mint(svm, backend, &mint)?.supply == supply,
+            Expect::MintAuthority(mint, authority) => {
+                unpack_mint(svm, backend, &mint)?.mint_authority == authority
+            }
+            Expect::FreezeAuthority(mint, authority) => {
+                unpack_mint(svm, backend, &mint)?.freeze_authority == authority
+            }
+            Expect::Amount(account, amount) => {
+                unpack_token_account(svm, backend, &account)?.amount == amount
+            }
+            Expect::Owner(account, owner) => {
+                unpack_token_account(svm, backend, &account)?.owner == owner
+            }
+            Expect::Delegate(account, delegate) => {
+                let state = unpack_token_account(svm, backend, &account)?;
+                match delegate {
+                    Some((delegate, amount)) => {
+                        state.delegate == Some(delegate) && state.delegated_amount == amount
+                    }
+                    None => state.delegate.is_none() && state.delegated_amount == 0,
+                }
+            }
+            Expect::State(account, state) => {
+                unpack_token_account(svm, backend, &account)?.state == state
+            }
+            Expect::CloseAuthority(account, authority) => {
+                unpack_token_account(svm, backend, &account)?.close_authority == authority
+            }
+            Expect::Multisig(multisig, m, n) => {
+                // Both programs share the SPL Token multisig layout
+                let data = account_data(svm, &multisig)?;
+                let state = spl_token::state::Multisig::unpack(&data)
+                    .map_err(|e| format!("multisig {}: {}", multisig, e))?;
+                state.m == m && state.n == n
+            }
+            Expect::ExtensionAuthority(mint, authority_type, authority) => {
+                extension_authority(svm, &mint, authority_type)? == authority
+            }
+            Expect::TransferFee(mint, basis_points, maximum_fee) => {
+                let fee = read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(mint
+                        .get_extension::<TransferFeeConfig>()?
+                        .newer_transfer_fee)
+                })?;
+                u16::from(fee.transfer_fee_basis_points) == basis_points
+                    && u64::from(fee.maximum_fee) == maximum_fee
+            }
+            Expect::MintWithheld(mint, amount) => {
+                read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(u64::from(
+                        mint.get_extension::<TransferFeeConfig>()?.withheld_amount,
+                    ))
+                })? == amount
+            }
+            Expect::HasMintExtension(mint, extension_type) => {
+                read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(mint.get_extension_types()?.contains(&extension_type))
+                })?
+            }
+            Expect::DefaultAccountState(mint, state) => {
+                read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(mint.get_extension::<DefaultAccountState>()?.state)
+                })? == state as u8
+            }
+            Expect::InterestRate(mint, rate) => {
+                read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(i16::from(
+                        mint.get_extension::<InterestBearingConfig>()?.current_rate,
+                    ))
+                })? == rate
+            }
+            Expect::NewMultiplier(mint, multiplier, effective_timestamp) => {
+                let config = read_token_2022_mint(svm, &mint, |mint| {
+                    mint.get_extension::<ScaledUiAmountConfig>().copied()
+                })?;
+                f64::from(config.new_multiplier) == multiplier
+                    && i64::from(config.new_multiplier_effective_timestamp) == effective_timestamp
+            }
+            Expect::Paused(mint, paused) => {
+                read_token_2022_mint(svm, &mint, |mint| {
+                    Ok(bool::from(mint.get_extension::<PausableConfig>()?.paused))
+                })? == paused
+            }
+            Expect::AccountWithheld(account, amount) => {
+                read_token_2022_account(svm, &account, |account| {
+                    Ok(u64::from(
+                        account
+                            .get_extension::<TransferFeeAmount>()?
+                            .withheld_amount,
+                    ))
+                })? == amount
+            }
+            Expect::MemoRequired(account, required) => {
+                read_token_2022_account(svm, &account, |account| {
+                    Ok(bool::from(
+                        account
+                            .get_extension::<MemoTransfer>()?
+                            .require_incoming_transfer_memos,
+                    ))
+                })? == required
+            }
+            Expect::Lamports(account, lamports) => {
+                svm.get_account(&account)
+                    .ok_or_else(|| format!("account {} does not exist", account))?
+                    .lamports
+                    == lamports
+            }
+            Expect::Closed(account) => svm
+                .get_account(&account)
+                .is_none_or(|account| account.lamports == 0),
+        };
+        if !matches {
+            return Err(format!("expected {:?}", expectation));
+        }
+    }
+    Ok(())
+}